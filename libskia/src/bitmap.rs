@@ -0,0 +1,189 @@
+use std::os::raw::c_void;
+
+use skia_safe::{Bitmap, Color, Color4f, IPoint, IRect, Image, ImageInfo, Pixmap};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+use crate::pixmap::{detach_pixmap, pixmap_contains};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_new() -> OwnedPtr<Bitmap> {
+    OwnedPtr::new(Bitmap::new())
+}
+
+/// Sets the image info of the bitmap and allocates pixel memory for it.
+/// Returns false if the image info is invalid or the allocation fails
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_alloc_pixels(
+    mut bitmap: BorrowedPtr<Bitmap>,
+    image_info: BorrowedPtr<ImageInfo>,
+) -> bool {
+    bitmap
+        .with_mut(|bitmap| {
+            image_info.with_ref_ok(|image_info| bitmap.try_alloc_pixels_info(image_info, None))
+        })
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_image_info(bitmap: BorrowedPtr<Bitmap>) -> OwnedPtr<ImageInfo> {
+    bitmap
+        .with_ref_ok(|bitmap| OwnedPtr::new(bitmap.info().clone()))
+        .or_log(OwnedPtr::new(ImageInfo::default()))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_width(bitmap: BorrowedPtr<Bitmap>) -> i32 {
+    bitmap.with_ref_ok(|bitmap| bitmap.width()).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_height(bitmap: BorrowedPtr<Bitmap>) -> i32 {
+    bitmap.with_ref_ok(|bitmap| bitmap.height()).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_row_bytes(bitmap: BorrowedPtr<Bitmap>) -> usize {
+    bitmap.with_ref_ok(|bitmap| bitmap.row_bytes()).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_compute_byte_size(bitmap: BorrowedPtr<Bitmap>) -> usize {
+    bitmap
+        .with_ref_ok(|bitmap| bitmap.compute_byte_size())
+        .or_log(0)
+}
+
+/// Returns the address of the first pixel or null if pixels are not allocated.
+/// The memory is owned by the bitmap and is only valid as long as the bitmap is alive
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_pixels(mut bitmap: BorrowedPtr<Bitmap>) -> *mut c_void {
+    bitmap
+        .with_mut_ok(|bitmap| bitmap.pixels())
+        .or_log(std::ptr::null_mut())
+}
+
+/// Must be called after the pixels are modified directly through the pointer
+/// returned by [`skia_bitmap_get_pixels`] so that cached copies are invalidated
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_notify_pixels_changed(bitmap: BorrowedPtr<Bitmap>) {
+    bitmap
+        .with_ref_ok(|bitmap| bitmap.notify_pixels_changed())
+        .log();
+}
+
+/// # Safety
+///
+/// The returned [`Pixmap`] points to the pixels of `bitmap` and must not outlive it.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_peek_pixels(
+    bitmap: BorrowedPtr<Bitmap>,
+) -> OwnedPtr<Pixmap<'static>> {
+    bitmap
+        .with_ref_ok(|bitmap| match bitmap.peek_pixels() {
+            None => OwnedPtr::null(),
+            Some(pixmap) => OwnedPtr::new(unsafe { detach_pixmap(pixmap) }),
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the color of the pixel at `x` and `y` or null if it is out of bounds
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_color(
+    bitmap: BorrowedPtr<Bitmap>,
+    x: i32,
+    y: i32,
+) -> OwnedPtr<Color> {
+    bitmap
+        .with_ref_ok(|bitmap| {
+            if pixmap_contains(bitmap.pixmap(), x, y) {
+                OwnedPtr::new(bitmap.get_color(IPoint::new(x, y)))
+            } else {
+                OwnedPtr::null()
+            }
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the unpremultiplied float color of the pixel at `x` and `y` or null if it is out of bounds
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_get_color4f(
+    bitmap: BorrowedPtr<Bitmap>,
+    x: i32,
+    y: i32,
+) -> OwnedPtr<Color4f> {
+    bitmap
+        .with_ref_ok(|bitmap| {
+            if pixmap_contains(bitmap.pixmap(), x, y) {
+                OwnedPtr::new(bitmap.get_color_4f(IPoint::new(x, y)))
+            } else {
+                OwnedPtr::null()
+            }
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Fills the `area` of the bitmap with a color, the whole bitmap is erased if `area` is null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_erase(
+    bitmap: BorrowedPtr<Bitmap>,
+    color: BorrowedPtr<Color>,
+    area: BorrowedPtr<IRect>, // may be null
+) {
+    bitmap
+        .with_ref(|bitmap| {
+            color.with_clone(|color| {
+                area.with_option_ref(|area| {
+                    match area {
+                        None => bitmap.erase_color(color),
+                        Some(area) => bitmap.erase(color, area),
+                    };
+                    Ok(())
+                })
+            })
+        })
+        .log();
+}
+
+/// Returns a bitmap that shares the pixels within `area` with the given bitmap
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_extract_subset(
+    bitmap: BorrowedPtr<Bitmap>,
+    area: BorrowedPtr<IRect>,
+) -> OwnedPtr<Bitmap> {
+    bitmap
+        .with_ref(|bitmap| {
+            area.with_ref_ok(|area| {
+                let mut subset = Bitmap::new();
+                if bitmap.extract_subset(&mut subset, area) {
+                    OwnedPtr::new(subset)
+                } else {
+                    OwnedPtr::null()
+                }
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns an image with the pixels of the bitmap. The pixels are copied unless the bitmap is immutable
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_as_image(bitmap: BorrowedPtr<Bitmap>) -> OwnedPtr<Image> {
+    bitmap
+        .with_ref_ok(|bitmap| {
+            if bitmap.draws_nothing() {
+                OwnedPtr::null()
+            } else {
+                OwnedPtr::new(bitmap.as_image())
+            }
+        })
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_set_immutable(mut bitmap: BorrowedPtr<Bitmap>) {
+    bitmap.with_mut_ok(|bitmap| bitmap.set_immutable()).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bitmap_drop(bitmap: OwnedPtr<Bitmap>) {
+    drop(bitmap);
+}
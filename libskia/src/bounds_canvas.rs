@@ -0,0 +1,98 @@
+use skia_safe::{Canvas, PictureRecorder, Rect};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+/// Draws are recorded within these bounds, so that nothing is rejected
+const UNBOUNDED: f32 = 1.0e9;
+
+/// A canvas that draws nothing and tracks the union of the device bounds of everything drawn
+/// on it, like a `SkNoDrawCanvas` that accumulates bounds. The draws are recorded with a
+/// bounding box hierarchy, so skia computes the bounds of each draw including the outsets
+/// of the paint effects such as blurs. The bounds are computed when they are first requested
+pub struct BoundsCanvas {
+    recorder: PictureRecorder,
+    bounds: Option<Option<Rect>>,
+}
+
+impl BoundsCanvas {
+    pub fn new() -> Self {
+        let mut recorder = PictureRecorder::new();
+        // skia bounds the transforms and clips outside of any save to the whole recording,
+        // inside a save they are bounded by the draws that follow them
+        recorder
+            .begin_recording(
+                Rect::new(-UNBOUNDED, -UNBOUNDED, UNBOUNDED, UNBOUNDED),
+                true,
+            )
+            .save();
+        Self {
+            recorder,
+            bounds: None,
+        }
+    }
+
+    /// The canvas to draw on, none once the bounds were computed
+    pub fn canvas(&mut self) -> Option<&Canvas> {
+        self.recorder.recording_canvas()
+    }
+
+    /// Returns the union of the device bounds of the draws, none if nothing was drawn
+    pub fn bounds(&mut self) -> Option<Rect> {
+        let recorder = &mut self.recorder;
+        *self.bounds.get_or_insert_with(|| {
+            // the cull rect of a picture recorded with a bounding box hierarchy
+            // shrinks to the union of the bounds of its draws
+            recorder
+                .finish_recording_as_picture(None)
+                .map(|picture| picture.cull_rect())
+                .filter(|bounds| !bounds.is_empty())
+        })
+    }
+}
+
+impl Default for BoundsCanvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bounds_canvas_new() -> OwnedPtr<BoundsCanvas> {
+    OwnedPtr::new(BoundsCanvas::new())
+}
+
+/// Returns the canvas to draw on, or null once the bounds were requested.
+///
+/// # Safety
+///
+/// The returned [`BorrowedPtr<Canvas>`] is borrowed from `bounds_canvas`
+/// and must not outlive it or be used after the bounds were requested.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bounds_canvas_get_canvas(
+    mut bounds_canvas: BorrowedPtr<BoundsCanvas>,
+) -> BorrowedPtr<Canvas> {
+    bounds_canvas
+        .with_mut_ok(|bounds_canvas| match bounds_canvas.canvas() {
+            None => BorrowedPtr::null(),
+            Some(canvas) => BorrowedPtr::from_ref(canvas),
+        })
+        .or_log(BorrowedPtr::null())
+}
+
+/// Returns the union of the device bounds of everything drawn, or null if nothing was drawn.
+/// Nothing can be drawn on the canvas afterwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bounds_canvas_get_bounds(
+    mut bounds_canvas: BorrowedPtr<BoundsCanvas>,
+) -> OwnedPtr<Rect> {
+    bounds_canvas
+        .with_mut_ok(|bounds_canvas| match bounds_canvas.bounds() {
+            None => OwnedPtr::null(),
+            Some(bounds) => OwnedPtr::new(bounds),
+        })
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_bounds_canvas_drop(bounds_canvas: OwnedPtr<BoundsCanvas>) {
+    drop(bounds_canvas);
+}
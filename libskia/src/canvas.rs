@@ -3,10 +3,11 @@ use float_cmp::ApproxEqUlps;
 use skia_safe::canvas::{PointMode, SaveLayerRec};
 use skia_safe::utils::shadow_utils::{ShadowFlags, draw_shadow};
 use skia_safe::{
-    BlendMode, Canvas, Color, FilterMode, Image, M44, Matrix, MipmapMode, Paint, Path, Point,
-    Point3, RRect, Rect, SamplingOptions, TextBlob, Vector, scalar,
+    BlendMode, Canvas, Color, Color4f, FilterMode, IPoint, Image, ImageInfo, M44, Matrix,
+    MipmapMode, Paint, Path, Point, Point3, RRect, Rect, Region, SamplingOptions, TextBlob, Vector,
+    scalar,
 };
-use value_box::{BorrowedPtr, ReturnBoxerResult};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 use crate::layer::SaveLayerRecWrapper;
 
@@ -19,6 +20,20 @@ pub extern "C" fn skia_canvas_clear(canvas: BorrowedPtr<Canvas>, r: u8, g: u8, b
         .log();
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_clear_color4f(
+    canvas: BorrowedPtr<Canvas>,
+    color: BorrowedPtr<Color4f>,
+) {
+    canvas
+        .with_ref(|canvas| {
+            color.with_clone_ok(|color| {
+                canvas.clear(color);
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_draw_color(
     canvas: BorrowedPtr<Canvas>,
@@ -252,6 +267,23 @@ pub extern "C" fn skia_canvas_draw_path(
         .log();
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_draw_region(
+    canvas: BorrowedPtr<Canvas>,
+    region: BorrowedPtr<Region>,
+    paint: BorrowedPtr<Paint>,
+) {
+    canvas
+        .with_ref(|canvas| {
+            paint.with_ref(|paint| {
+                region.with_ref_ok(|region| {
+                    canvas.draw_region(region, paint);
+                })
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_draw_text_blob(
     canvas: BorrowedPtr<Canvas>,
@@ -406,6 +438,17 @@ pub extern "C" fn skia_canvas_concat_matrix(
         .log();
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_concat_m44(canvas: BorrowedPtr<Canvas>, m44: BorrowedPtr<M44>) {
+    canvas
+        .with_ref(|canvas| {
+            m44.with_ref_ok(|m44| {
+                canvas.concat_44(m44);
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_set_matrix(canvas: BorrowedPtr<Canvas>, matrix: BorrowedPtr<Matrix>) {
     canvas
@@ -438,6 +481,23 @@ pub extern "C" fn skia_canvas_get_matrix(
         .log();
 }
 
+/// Returns the 3x3 part of the current transform, use [`skia_canvas_get_local_to_device_m44`] to include z
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_get_total_matrix(canvas: BorrowedPtr<Canvas>) -> OwnedPtr<Matrix> {
+    canvas
+        .with_ref_ok(|canvas| OwnedPtr::new(canvas.local_to_device_as_3x3()))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_get_local_to_device_m44(
+    canvas: BorrowedPtr<Canvas>,
+) -> OwnedPtr<M44> {
+    canvas
+        .with_ref_ok(|canvas| OwnedPtr::new(canvas.local_to_device()))
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_reset_matrix(canvas: BorrowedPtr<Canvas>) {
     canvas
@@ -505,6 +565,33 @@ pub extern "C" fn skia_canvas_save_layer(
         .or_log(0)
 }
 
+/// Reads a rectangle of pixels starting at `src_x` and `src_y` into `pixels` ignoring matrix and clip.
+/// Returns false if the canvas is not backed by pixels, for example when recording a picture
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_read_pixels(
+    canvas: BorrowedPtr<Canvas>,
+    src_x: i32,
+    src_y: i32,
+    image_info: BorrowedPtr<ImageInfo>,
+    mut pixels: BorrowedPtr<ArrayBox<u8>>,
+    row_bytes: usize,
+) -> bool {
+    canvas
+        .with_ref(|canvas| {
+            image_info.with_ref(|image_info| {
+                pixels.with_mut_ok(|pixels| {
+                    canvas.read_pixels(
+                        image_info,
+                        pixels.to_slice_mut(),
+                        row_bytes,
+                        IPoint::new(src_x, src_y),
+                    )
+                })
+            })
+        })
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_drop(canvas: BorrowedPtr<Canvas>) {
     let _ = canvas;
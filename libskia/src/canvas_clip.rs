@@ -1,5 +1,7 @@
 use float_cmp::{ApproxEq, F32Margin};
-use skia_safe::{Canvas, ClipOp, IRect, Path, QuickReject, RRect, Rect, Vector, scalar};
+use skia_safe::{
+    Canvas, ClipOp, IRect, Path, QuickReject, RRect, Rect, Region, Shader, Vector, scalar,
+};
 use value_box::{BorrowedPtr, ReturnBoxerResult};
 
 #[unsafe(no_mangle)]
@@ -149,6 +151,100 @@ pub extern "C" fn skia_canvas_clip_oval(
         .log();
 }
 
+/// Clips the canvas with a region that is given in device coordinates and is not affected by the matrix
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_clip_region(
+    canvas: BorrowedPtr<Canvas>,
+    region: BorrowedPtr<Region>,
+    clip_op: ClipOp,
+) {
+    canvas
+        .with_ref(|canvas| {
+            region.with_ref_ok(|region| {
+                canvas.clip_region(region, clip_op);
+            })
+        })
+        .log();
+}
+
+/// Clips the canvas with the alpha of the shader, which allows soft edges such as gradient fades.
+/// The shader is transformed by the current matrix
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_clip_shader(
+    canvas: BorrowedPtr<Canvas>,
+    shader: BorrowedPtr<Shader>,
+    clip_op: ClipOp,
+) {
+    canvas
+        .with_ref(|canvas| {
+            shader.with_clone_ok(|shader| {
+                canvas.clip_shader(shader, clip_op);
+            })
+        })
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_is_clip_empty(canvas: BorrowedPtr<Canvas>) -> bool {
+    canvas
+        .with_ref_ok(|canvas| canvas.is_clip_empty())
+        .or_log(false)
+}
+
+/// Returns true if the clip is a non-empty device space rectangle
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_is_clip_rect(canvas: BorrowedPtr<Canvas>) -> bool {
+    canvas
+        .with_ref_ok(|canvas| canvas.is_clip_rect())
+        .or_log(false)
+}
+
+/// Skia does not expose the shape of complex clips, so for clips that are neither empty nor rectangular
+/// the region is set to the device clip bounds. Returns true if the region matches the clip exactly
+fn device_clip_as_region(canvas: &Canvas, region: &mut Region) -> bool {
+    match canvas.device_clip_bounds() {
+        None => {
+            region.set_empty();
+        }
+        Some(bounds) => {
+            region.set_rect(bounds);
+        }
+    };
+    canvas.is_clip_empty() || canvas.is_clip_rect()
+}
+
+/// Sets `region` to the device clip of the canvas, complex clips are approximated by their bounds.
+/// Returns true if the region matches the clip exactly
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_get_device_clip_as_region(
+    canvas: BorrowedPtr<Canvas>,
+    mut region: BorrowedPtr<Region>,
+) -> bool {
+    canvas
+        .with_ref(|canvas| region.with_mut_ok(|region| device_clip_as_region(canvas, region)))
+        .or_log(false)
+}
+
+/// Sets `path` to the outline of the device clip, complex clips are approximated by their bounds.
+/// Returns true if the path matches the clip exactly
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_get_device_clip_as_path(
+    canvas: BorrowedPtr<Canvas>,
+    mut path: BorrowedPtr<Path>,
+) -> bool {
+    canvas
+        .with_ref(|canvas| {
+            path.with_mut_ok(|path| {
+                let mut region = Region::new();
+                let is_exact = device_clip_as_region(canvas, &mut region);
+                path.reset();
+                region.get_boundary_path(path);
+                is_exact
+            })
+        })
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_local_clip_bounds(
     canvas: BorrowedPtr<Canvas>,
@@ -1,5 +1,5 @@
 use array_box::ArrayBox;
-use skia_safe::Color;
+use skia_safe::{Color, HSV, RGB};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 #[unsafe(no_mangle)]
@@ -37,6 +37,22 @@ pub extern "C" fn skia_color_get_alpha(color: BorrowedPtr<Color>) -> u8 {
     color.with_clone_ok(Color::a).or_log(0)
 }
 
+/// Writes the hue in degrees `[0, 360)`, the saturation and the value in `[0, 1]` into `hsv`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_rgb_to_hsv(r: u8, g: u8, b: u8, mut hsv: BorrowedPtr<ArrayBox<f32>>) {
+    hsv.with_mut_ok(|hsv| {
+        let HSV { h, s, v } = RGB::from((r, g, b)).to_hsv();
+        hsv.set_array(&[h, s, v]);
+    })
+    .log();
+}
+
+/// Converts a hue in degrees, a saturation and a value to a color, out of range components are clamped
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_hsv_to_color(h: f32, s: f32, v: f32, a: u8) -> OwnedPtr<Color> {
+    OwnedPtr::new(HSV::from((h, s, v)).to_color(a))
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_color_drop(color: OwnedPtr<Color>) {
     drop(color);
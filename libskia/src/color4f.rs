@@ -0,0 +1,174 @@
+use array_box::ArrayBox;
+use skia_safe::{Color, Color4f};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_default() -> OwnedPtr<Color4f> {
+    OwnedPtr::new(Color4f::new(0.0, 0.0, 0.0, 0.0))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_create(r: f32, g: f32, b: f32, a: f32) -> OwnedPtr<Color4f> {
+    OwnedPtr::new(Color4f::new(r, g, b, a))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_from_color(color: BorrowedPtr<Color>) -> OwnedPtr<Color4f> {
+    color
+        .with_clone_ok(|color| OwnedPtr::new(Color4f::from(color)))
+        .or_log(OwnedPtr::null())
+}
+
+/// Converts to an 8-bit color, components outside of the `[0, 1]` range are clamped
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_to_color(color: BorrowedPtr<Color4f>) -> OwnedPtr<Color> {
+    color
+        .with_clone_ok(|color| OwnedPtr::new(color.to_color()))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_get_red(color: BorrowedPtr<Color4f>) -> f32 {
+    color.with_ref_ok(|color| color.r).or_log(0.0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_get_green(color: BorrowedPtr<Color4f>) -> f32 {
+    color.with_ref_ok(|color| color.g).or_log(0.0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_get_blue(color: BorrowedPtr<Color4f>) -> f32 {
+    color.with_ref_ok(|color| color.b).or_log(0.0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_get_alpha(color: BorrowedPtr<Color4f>) -> f32 {
+    color.with_ref_ok(|color| color.a).or_log(0.0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_set_red(mut color: BorrowedPtr<Color4f>, red: f32) {
+    color.with_mut_ok(|color| color.r = red).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_set_green(mut color: BorrowedPtr<Color4f>, green: f32) {
+    color.with_mut_ok(|color| color.g = green).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_set_blue(mut color: BorrowedPtr<Color4f>, blue: f32) {
+    color.with_mut_ok(|color| color.b = blue).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_set_alpha(mut color: BorrowedPtr<Color4f>, alpha: f32) {
+    color.with_mut_ok(|color| color.a = alpha).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_is_opaque(color: BorrowedPtr<Color4f>) -> bool {
+    color.with_ref_ok(|color| color.is_opaque()).or_log(false)
+}
+
+/// Returns a color with the red, green and blue components multiplied by alpha
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_premul(color: BorrowedPtr<Color4f>) -> OwnedPtr<Color4f> {
+    color
+        .with_clone_ok(|color| {
+            OwnedPtr::new(Color4f::new(
+                color.r * color.a,
+                color.g * color.a,
+                color.b * color.a,
+                color.a,
+            ))
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns a color with the red, green and blue components divided by alpha.
+/// A fully transparent color is unpremultiplied to transparent black
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_unpremul(color: BorrowedPtr<Color4f>) -> OwnedPtr<Color4f> {
+    color
+        .with_clone_ok(|color| {
+            if color.a == 0.0 {
+                OwnedPtr::new(Color4f::new(0.0, 0.0, 0.0, 0.0))
+            } else {
+                let inverse_alpha = 1.0 / color.a;
+                OwnedPtr::new(Color4f::new(
+                    color.r * inverse_alpha,
+                    color.g * inverse_alpha,
+                    color.b * inverse_alpha,
+                    color.a,
+                ))
+            }
+        })
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_drop(color: OwnedPtr<Color4f>) {
+    drop(color);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_default() -> OwnedPtr<ArrayBox<Color4f>> {
+    OwnedPtr::new(ArrayBox::new())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_create_with(
+    color: BorrowedPtr<Color4f>,
+    amount: usize,
+) -> OwnedPtr<ArrayBox<Color4f>> {
+    color
+        .with_clone_ok(|color| OwnedPtr::new(ArrayBox::from_vector(vec![color; amount])))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_get_length(array: BorrowedPtr<ArrayBox<Color4f>>) -> usize {
+    array.with_ref_ok(|array| array.length).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_get_capacity(array: BorrowedPtr<ArrayBox<Color4f>>) -> usize {
+    array.with_ref_ok(|array| array.capacity).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_get_data(
+    array: BorrowedPtr<ArrayBox<Color4f>>,
+) -> *mut Color4f {
+    array
+        .with_ref_ok(|array| array.data)
+        .or_log(std::ptr::null_mut())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_at(
+    array: BorrowedPtr<ArrayBox<Color4f>>,
+    index: usize,
+) -> OwnedPtr<Color4f> {
+    array
+        .with_ref_ok(|array| OwnedPtr::new(array.at(index)))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_at_put(
+    mut array: BorrowedPtr<ArrayBox<Color4f>>,
+    index: usize,
+    color: BorrowedPtr<Color4f>,
+) {
+    color
+        .with_clone(|color| array.with_mut_ok(|array| array.at_put(index, color)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color4f_array_drop(array: OwnedPtr<ArrayBox<Color4f>>) {
+    drop(array);
+}
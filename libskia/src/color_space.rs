@@ -1,11 +1,415 @@
-use skia_safe::ColorSpace;
+use array_box::ArrayBox;
+use skia_safe::{ColorSpace, ColorSpacePrimaries, ColorSpaceTransferFn, Data, EncodedImageFormat};
+use skia_safe::{ImageInfo, images, named_primaries, named_transfer_fn};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
+/// Size of the header that precedes the transfer function and the gamut in a serialized color space
+const SERIALIZED_HEADER_SIZE: usize = 4;
+/// A serialized color space is a header followed by 7 transfer function and 9 matrix floats
+const SERIALIZED_SIZE: usize = SERIALIZED_HEADER_SIZE + 16 * size_of::<f32>();
+
+/// Identifies the ICC profile in the APP2 segments of a JPEG
+const JPEG_ICC_MARKER: &[u8; 12] = b"ICC_PROFILE\0";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum ColorSpaceGamut {
+    Srgb,
+    AdobeRgb,
+    DisplayP3,
+    Rec2020,
+    Xyz,
+}
+
+impl ColorSpaceGamut {
+    /// Adobe RGB and the XYZ D50 identity gamut have no CICP primaries,
+    /// which is the only way skia exposes to create a color space with a named gamut
+    fn to_cicp(self) -> Option<named_primaries::CicpId> {
+        match self {
+            ColorSpaceGamut::Srgb => Some(named_primaries::CicpId::Rec709),
+            ColorSpaceGamut::AdobeRgb => None,
+            ColorSpaceGamut::DisplayP3 => Some(named_primaries::CicpId::SMPTE_EG_432_1),
+            ColorSpaceGamut::Rec2020 => Some(named_primaries::CicpId::Rec2020),
+            ColorSpaceGamut::Xyz => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum ColorSpaceTransferFunction {
+    Srgb,
+    TwoDotTwo,
+    Linear,
+    Rec2020,
+    Pq,
+    Hlg,
+}
+
+/// The CICP primaries of the named primaries with the same chromaticities
+const NAMED_PRIMARIES: [(ColorSpacePrimaries, named_primaries::CicpId); 10] = [
+    (named_primaries::REC709, named_primaries::CicpId::Rec709),
+    (
+        named_primaries::REC470_SYSTEM_M,
+        named_primaries::CicpId::Rec470SystemM,
+    ),
+    (
+        named_primaries::REC470_SYSTEM_BG,
+        named_primaries::CicpId::Rec470SystemBG,
+    ),
+    (named_primaries::REC601, named_primaries::CicpId::Rec601),
+    (
+        named_primaries::GENERIC_FILM,
+        named_primaries::CicpId::GenericFilm,
+    ),
+    (named_primaries::REC2020, named_primaries::CicpId::Rec2020),
+    (
+        named_primaries::SMPTE_ST_428_1,
+        named_primaries::CicpId::SMPTE_ST_428_1,
+    ),
+    (
+        named_primaries::SMPTE_RP_431_2,
+        named_primaries::CicpId::SMPTE_RP_431_2,
+    ),
+    (
+        named_primaries::SMPTE_EG_432_1,
+        named_primaries::CicpId::SMPTE_EG_432_1,
+    ),
+    (
+        named_primaries::ITU_T_H273_VALUE22,
+        named_primaries::CicpId::ITU_T_H273_Value22,
+    ),
+];
+
+/// Returns the CICP primaries with the given chromaticities, up to the precision of
+/// the 4 decimals in which ITU-T H.273 lists them
+fn primaries_to_cicp(primaries: &ColorSpacePrimaries) -> Option<named_primaries::CicpId> {
+    let chromaticities = |primaries: &ColorSpacePrimaries| {
+        [
+            primaries.rx,
+            primaries.ry,
+            primaries.gx,
+            primaries.gy,
+            primaries.bx,
+            primaries.by,
+            primaries.wx,
+            primaries.wy,
+        ]
+    };
+    let expected = chromaticities(primaries);
+    NAMED_PRIMARIES
+        .iter()
+        .find(|(named, _)| {
+            chromaticities(named)
+                .iter()
+                .zip(expected)
+                .all(|(named, expected)| (named - expected).abs() < 0.0001)
+        })
+        .map(|(_, cicp)| *cicp)
+}
+
+/// Creates a color space with the CICP primaries and the transfer function,
+/// the linear variant is derived from the sRGB one
+fn new_rgb_color_space(
+    transfer_function: ColorSpaceTransferFunction,
+    primaries: named_primaries::CicpId,
+) -> Option<ColorSpace> {
+    let with_transfer_fn = |transfer_fn| ColorSpace::new_cicp(primaries, transfer_fn);
+    match transfer_function {
+        ColorSpaceTransferFunction::Srgb => {
+            with_transfer_fn(named_transfer_fn::CicpId::IEC61966_2_1)
+        }
+        ColorSpaceTransferFunction::Linear => {
+            with_transfer_fn(named_transfer_fn::CicpId::IEC61966_2_1)
+                .map(|color_space| color_space.with_linear_gamma())
+        }
+        ColorSpaceTransferFunction::TwoDotTwo => {
+            with_transfer_fn(named_transfer_fn::CicpId::Rec470SystemM)
+        }
+        ColorSpaceTransferFunction::Rec2020 => {
+            with_transfer_fn(named_transfer_fn::CicpId::Rec2020_12bit)
+        }
+        ColorSpaceTransferFunction::Pq => with_transfer_fn(named_transfer_fn::CicpId::PQ),
+        ColorSpaceTransferFunction::Hlg => with_transfer_fn(named_transfer_fn::CicpId::HLG),
+    }
+}
+
+fn transfer_fn_to_array(transfer_fn: &ColorSpaceTransferFn) -> [f32; 7] {
+    [
+        transfer_fn.g,
+        transfer_fn.a,
+        transfer_fn.b,
+        transfer_fn.c,
+        transfer_fn.d,
+        transfer_fn.e,
+        transfer_fn.f,
+    ]
+}
+
+/// Checks that the serialized color space has the layout and values that skia accepts,
+/// because [`ColorSpace::deserialize`] panics when skia fails to create a color space.
+/// The transfer function is classified the way skcms does it and the gamut must be invertible,
+/// which are all the checks of `SkColorSpace::Deserialize`
+fn is_valid_serialized_color_space(bytes: &[u8]) -> bool {
+    if bytes.len() < SERIALIZED_SIZE {
+        return false;
+    }
+    let reference = ColorSpace::new_srgb().serialize();
+    if bytes[..SERIALIZED_HEADER_SIZE] != reference.as_bytes()[..SERIALIZED_HEADER_SIZE] {
+        return false;
+    }
+
+    let floats = read_floats(&bytes[SERIALIZED_HEADER_SIZE..SERIALIZED_SIZE]);
+    if floats.iter().any(|value| !value.is_finite()) {
+        return false;
+    }
+
+    let [g, a, b, c, d, e, f] = floats[..7] else {
+        return false;
+    };
+    let is_valid_transfer_fn = if g < 0.0 {
+        // negative gammas tag the PQ and HLG families of transfer functions
+        g.fract() == 0.0 && (-6.0..=-2.0).contains(&g)
+    } else {
+        (a + b + c + d + e + f + g).is_finite()
+            && a >= 0.0
+            && c >= 0.0
+            && d >= 0.0
+            // a negative base raised to a fractional gamma is not a real number
+            && a * d + b >= 0.0
+    };
+
+    let matrix = [
+        [floats[7], floats[8], floats[9]],
+        [floats[10], floats[11], floats[12]],
+        [floats[13], floats[14], floats[15]],
+    ]
+    .map(|row| row.map(|value| value as f64));
+
+    is_valid_transfer_fn && is_invertible(&matrix)
+}
+
+fn is_invertible(m: &[[f64; 3]; 3]) -> bool {
+    let determinant = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    determinant.is_finite() && determinant.abs() >= f64::EPSILON
+}
+
+fn read_floats(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(size_of::<f32>())
+        .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn deserialize_color_space(bytes: &[u8]) -> Option<ColorSpace> {
+    if !is_valid_serialized_color_space(bytes) {
+        return None;
+    }
+    Some(ColorSpace::deserialize(Data::new_copy(bytes)))
+}
+
+/// Returns the row-major matrix that converts linear RGB of the color space to XYZ D50,
+/// it follows the transfer function in the serialized color space
+fn color_space_to_xyz_d50(color_space: &ColorSpace) -> Option<[f32; 9]> {
+    let data = color_space.serialize();
+    let bytes = data.as_bytes();
+    if !is_valid_serialized_color_space(bytes) {
+        return None;
+    }
+    read_floats(&bytes[SERIALIZED_HEADER_SIZE + 7 * size_of::<f32>()..SERIALIZED_SIZE])
+        .try_into()
+        .ok()
+}
+
+/// Returns the ICC profile that skia embeds when encoding an image in the color space as JPEG,
+/// none if skia can not describe the color space with an ICC profile
+fn color_space_to_icc(color_space: &ColorSpace) -> Option<Vec<u8>> {
+    let image_info = ImageInfo::new_n32_premul((1, 1), Some(color_space.clone()));
+    let pixels = vec![0u8; image_info.min_row_bytes()];
+    let image = images::raster_from_data(
+        &image_info,
+        Data::new_copy(&pixels),
+        image_info.min_row_bytes(),
+    )?;
+    let jpeg = image.encode(None, EncodedImageFormat::JPEG, 100)?;
+    jpeg_icc_profile(jpeg.as_bytes())
+}
+
+/// Joins the chunks of the ICC profile stored in the APP2 segments of a JPEG,
+/// each chunk is preceded by the marker, its 1-based sequence number and the amount of chunks
+fn jpeg_icc_profile(jpeg: &[u8]) -> Option<Vec<u8>> {
+    let mut chunks = vec![];
+    let mut offset = 2;
+    while let Some([0xFF, marker, high, low]) = jpeg
+        .get(offset..offset + 4)
+        .map(|bytes| [bytes[0], bytes[1], bytes[2], bytes[3]])
+    {
+        // the image data follows the start of scan, there are no more segments
+        if marker == 0xDA {
+            break;
+        }
+        let length = u16::from_be_bytes([high, low]) as usize;
+        let segment = jpeg.get(offset + 4..offset + 2 + length)?;
+        let chunk = segment
+            .strip_prefix(JPEG_ICC_MARKER.as_slice())
+            .filter(|chunk| marker == 0xE2 && chunk.len() >= 2);
+        if let Some(chunk) = chunk {
+            chunks.push((chunk[0], &chunk[2..]));
+        }
+        offset += 2 + length;
+    }
+    if chunks.is_empty() {
+        return None;
+    }
+    chunks.sort_by_key(|(sequence_number, _)| *sequence_number);
+    Some(
+        chunks
+            .into_iter()
+            .flat_map(|(_, chunk)| chunk.iter().copied())
+            .collect(),
+    )
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_color_space_new_srgb() -> OwnedPtr<ColorSpace> {
     OwnedPtr::new(ColorSpace::new_srgb())
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_new_srgb_linear() -> OwnedPtr<ColorSpace> {
+    OwnedPtr::new(ColorSpace::new_srgb_linear())
+}
+
+/// Creates a color space from a named transfer function and a named gamut.
+/// Returns null for the Adobe RGB and XYZ gamuts, which skia can not create by name
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_new_rgb(
+    transfer_function: ColorSpaceTransferFunction,
+    gamut: ColorSpaceGamut,
+) -> OwnedPtr<ColorSpace> {
+    match gamut
+        .to_cicp()
+        .and_then(|primaries| new_rgb_color_space(transfer_function, primaries))
+    {
+        None => OwnedPtr::null(),
+        Some(color_space) => OwnedPtr::new(color_space),
+    }
+}
+
+/// Creates a color space from a named transfer function and the chromaticities
+/// of the red, green and blue primaries and of the white point.
+/// Returns null unless the chromaticities are the primaries of ITU-T H.273,
+/// skia can not create a color space with custom primaries
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_new_rgb_with_primaries(
+    transfer_function: ColorSpaceTransferFunction,
+    red_x: f32,
+    red_y: f32,
+    green_x: f32,
+    green_y: f32,
+    blue_x: f32,
+    blue_y: f32,
+    white_x: f32,
+    white_y: f32,
+) -> OwnedPtr<ColorSpace> {
+    let primaries = ColorSpacePrimaries {
+        rx: red_x,
+        ry: red_y,
+        gx: green_x,
+        gy: green_y,
+        bx: blue_x,
+        by: blue_y,
+        wx: white_x,
+        wy: white_y,
+    };
+    match primaries_to_cicp(&primaries)
+        .and_then(|primaries| new_rgb_color_space(transfer_function, primaries))
+    {
+        None => OwnedPtr::null(),
+        Some(color_space) => OwnedPtr::new(color_space),
+    }
+}
+
+/// Creates a color space from the contents of an ICC profile.
+/// Returns null if the profile can not be parsed or is not an RGB profile
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_new_from_icc(
+    data: BorrowedPtr<ArrayBox<u8>>,
+) -> OwnedPtr<ColorSpace> {
+    data.with_ref_ok(|data| match ColorSpace::new_icc(data.to_slice()) {
+        None => OwnedPtr::null(),
+        Some(color_space) => OwnedPtr::new(color_space),
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Writes an ICC profile describing the color space into `data`.
+/// Returns false if skia can not describe the color space with an ICC profile
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_to_icc(
+    color_space: BorrowedPtr<ColorSpace>,
+    mut data: BorrowedPtr<ArrayBox<u8>>,
+) -> bool {
+    color_space
+        .with_ref(|color_space| {
+            data.with_mut_ok(|data| match color_space_to_icc(color_space) {
+                None => false,
+                Some(profile) => {
+                    data.set_vector(profile);
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+/// Writes the row-major matrix that converts linear RGB of the color space to XYZ D50 into `matrix`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_to_xyz_d50(
+    color_space: BorrowedPtr<ColorSpace>,
+    mut matrix: BorrowedPtr<ArrayBox<f32>>,
+) -> bool {
+    color_space
+        .with_ref(|color_space| {
+            matrix.with_mut_ok(|matrix| match color_space_to_xyz_d50(color_space) {
+                None => false,
+                Some(to_xyz_d50) => {
+                    matrix.set_array(&to_xyz_d50);
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_serialize(
+    color_space: BorrowedPtr<ColorSpace>,
+    mut data: BorrowedPtr<ArrayBox<u8>>,
+) {
+    color_space
+        .with_ref(|color_space| {
+            data.with_mut_ok(|data| {
+                data.set_array(color_space.serialize().as_bytes());
+            })
+        })
+        .log();
+}
+
+/// Returns null if the data is not a color space serialized by [`skia_color_space_serialize`]
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_deserialize(
+    data: BorrowedPtr<ArrayBox<u8>>,
+) -> OwnedPtr<ColorSpace> {
+    data.with_ref_ok(|data| match deserialize_color_space(data.to_slice()) {
+        None => OwnedPtr::null(),
+        Some(color_space) => OwnedPtr::new(color_space),
+    })
+    .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_color_space_is_srgb(color_space: BorrowedPtr<ColorSpace>) -> bool {
     color_space
@@ -13,6 +417,40 @@ pub extern "C" fn skia_color_space_is_srgb(color_space: BorrowedPtr<ColorSpace>)
         .or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_gamma_is_linear(color_space: BorrowedPtr<ColorSpace>) -> bool {
+    color_space
+        .with_ref_ok(|color_space| color_space.transfer_fn() == named_transfer_fn::LINEAR)
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_equals(
+    color_space: BorrowedPtr<ColorSpace>,
+    other_color_space: BorrowedPtr<ColorSpace>,
+) -> bool {
+    color_space
+        .with_ref(|color_space| {
+            other_color_space.with_ref_ok(|other_color_space| color_space == other_color_space)
+        })
+        .or_log(false)
+}
+
+/// Writes the `g`, `a`, `b`, `c`, `d`, `e` and `f` coefficients of the transfer function into `coefficients`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_color_space_get_transfer_fn(
+    color_space: BorrowedPtr<ColorSpace>,
+    mut coefficients: BorrowedPtr<ArrayBox<f32>>,
+) {
+    color_space
+        .with_ref(|color_space| {
+            coefficients.with_mut_ok(|coefficients| {
+                coefficients.set_array(&transfer_fn_to_array(&color_space.transfer_fn()));
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_color_space_drop(color_space: OwnedPtr<ColorSpace>) {
     drop(color_space);
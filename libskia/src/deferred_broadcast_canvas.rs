@@ -0,0 +1,126 @@
+use std::ptr::NonNull;
+
+use skia_safe::{Canvas, ISize, PictureRecorder, Rect};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+/// Draws everything drawn on its canvas on several target canvases, but unlike Skia's `SkNWayCanvas`
+/// the calls are not forwarded as they are made. They are recorded into a picture and played back
+/// on the targets, in the order they were added, when the canvas is flushed.
+/// So the targets show nothing until then, the transform and the clip queried from the canvas are
+/// those of the recording and each flush starts over with the identity transform, no clip and no saves
+pub struct DeferredBroadcastCanvas {
+    recorder: PictureRecorder,
+    bounds: Rect,
+    targets: Vec<NonNull<Canvas>>,
+}
+
+impl DeferredBroadcastCanvas {
+    pub fn new(size: ISize) -> Self {
+        let bounds = Rect::from_isize(size);
+        let mut recorder = PictureRecorder::new();
+        recorder.begin_recording(bounds, false);
+        Self {
+            recorder,
+            bounds,
+            targets: vec![],
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The target must stay valid until it is removed or the broadcast canvas is dropped
+    pub unsafe fn add_target(&mut self, target: &Canvas) {
+        self.targets.push(NonNull::from(target));
+    }
+
+    pub fn remove_all_targets(&mut self) {
+        self.targets.clear();
+    }
+
+    pub fn canvas(&mut self) -> Option<&Canvas> {
+        self.recorder.recording_canvas()
+    }
+
+    /// Plays back what was drawn since the last flush on every target and starts over.
+    /// The transform, the clip and the saves of the canvas are discarded, the unbalanced saves
+    /// are restored on the targets by the playback
+    pub fn flush(&mut self) {
+        if let Some(picture) = self.recorder.finish_recording_as_picture(None) {
+            for target in &self.targets {
+                // the targets are valid as required by `add_target`
+                picture.playback(unsafe { target.as_ref() });
+            }
+        }
+        self.recorder.begin_recording(self.bounds, false);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_deferred_broadcast_canvas_new(
+    width: i32,
+    height: i32,
+) -> OwnedPtr<DeferredBroadcastCanvas> {
+    OwnedPtr::new(DeferredBroadcastCanvas::new(ISize::new(width, height)))
+}
+
+/// Adds a canvas that everything drawn on the broadcast canvas is played back on when flushed.
+///
+/// # Safety
+///
+/// The `target` must not be dropped before it is removed with
+/// [`skia_deferred_broadcast_canvas_remove_all_targets`] or the broadcast canvas is dropped.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_deferred_broadcast_canvas_add_target(
+    mut broadcast_canvas: BorrowedPtr<DeferredBroadcastCanvas>,
+    target: BorrowedPtr<Canvas>,
+) {
+    broadcast_canvas
+        .with_mut(|broadcast_canvas| {
+            target.with_ref_ok(|target| unsafe { broadcast_canvas.add_target(target) })
+        })
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_deferred_broadcast_canvas_remove_all_targets(
+    mut broadcast_canvas: BorrowedPtr<DeferredBroadcastCanvas>,
+) {
+    broadcast_canvas
+        .with_mut_ok(|broadcast_canvas| broadcast_canvas.remove_all_targets())
+        .log();
+}
+
+/// # Safety
+///
+/// The returned [`BorrowedPtr<Canvas>`] is borrowed from `broadcast_canvas`
+/// and must not outlive it or be used after [`skia_deferred_broadcast_canvas_flush`].
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_deferred_broadcast_canvas_get_canvas(
+    mut broadcast_canvas: BorrowedPtr<DeferredBroadcastCanvas>,
+) -> BorrowedPtr<Canvas> {
+    broadcast_canvas
+        .with_mut_ok(|broadcast_canvas| match broadcast_canvas.canvas() {
+            None => BorrowedPtr::null(),
+            Some(canvas) => BorrowedPtr::from_ref(canvas),
+        })
+        .or_log(BorrowedPtr::null())
+}
+
+/// Draws everything drawn since the last flush on the targets and starts over with
+/// the identity transform, no clip and no saves.
+/// What is not flushed when the broadcast canvas is dropped is discarded
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_deferred_broadcast_canvas_flush(
+    mut broadcast_canvas: BorrowedPtr<DeferredBroadcastCanvas>,
+) {
+    broadcast_canvas
+        .with_mut_ok(|broadcast_canvas| broadcast_canvas.flush())
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_deferred_broadcast_canvas_drop(
+    broadcast_canvas: OwnedPtr<DeferredBroadcastCanvas>,
+) {
+    drop(broadcast_canvas);
+}
@@ -0,0 +1,42 @@
+use skia_safe::{Canvas, Drawable, Matrix, Rect};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_drawable_get_bounds(mut drawable: BorrowedPtr<Drawable>) -> OwnedPtr<Rect> {
+    drawable
+        .with_mut_ok(|drawable| OwnedPtr::new(drawable.bounds()))
+        .or_log(OwnedPtr::null())
+}
+
+/// Draws the drawable transformed by `matrix`, which may be null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_drawable_draw(
+    mut drawable: BorrowedPtr<Drawable>,
+    canvas: BorrowedPtr<Canvas>,
+    matrix: BorrowedPtr<Matrix>, // may be null
+) {
+    drawable
+        .with_mut(|drawable| {
+            canvas.with_ref(|canvas| {
+                matrix.with_option_ref(|matrix| {
+                    drawable.draw(canvas, matrix);
+                    Ok(())
+                })
+            })
+        })
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_drawable_approximate_bytes_used(
+    mut drawable: BorrowedPtr<Drawable>,
+) -> usize {
+    drawable
+        .with_mut_ok(|drawable| drawable.approximate_bytes_used())
+        .or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_drawable_drop(drawable: OwnedPtr<Drawable>) {
+    drop(drawable);
+}
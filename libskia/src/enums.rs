@@ -1,16 +1,25 @@
 use skia_safe::canvas::PointMode;
 use skia_safe::font::Edging;
 use skia_safe::font_style::Slant;
+use skia_safe::image::CachingHint;
+use skia_safe::images::BitDepth;
+use skia_safe::matrix::ScaleToFit;
 use skia_safe::paint::{Cap, Join, Style};
+use skia_safe::region::RegionOp;
 use skia_safe::rrect::{Corner as RRectCorner, Type as RRectType};
+#[cfg(feature = "svg")]
+use skia_safe::svg::Visibility as SvgVisibility;
 use skia_safe::textlayout::PlaceholderAlignment;
+use skia_safe::typeface::SerializeBehavior;
+use skia_safe::utils::text_utils::Align as TextUtilsAlign;
 use skia_safe::{
-    AlphaType, BlendMode, ClipOp, ColorType, FontHinting, PathFillType, PixelGeometry,
-    TextEncoding, TileMode,
+    AlphaType, BlendMode, ClipOp, ColorType, FilterMode, FontHinting, MipmapMode, PathFillType,
+    PixelGeometry, TextEncoding, TileMode,
 };
 use string_box::StringBox;
 use value_box::{BorrowedPtr, ReturnBoxerResult};
 
+use crate::color_space::{ColorSpaceGamut, ColorSpaceTransferFunction};
 use crate::text::font_style::FontStyleWidth;
 
 #[unsafe(no_mangle)]
@@ -113,6 +122,56 @@ pub extern "C" fn skia_enums_tile_mode_to_string(
         .log();
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_filter_mode_to_string(
+    enum_value: FilterMode,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_mipmap_mode_to_string(
+    enum_value: MipmapMode,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_caching_hint_to_string(
+    enum_value: CachingHint,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_color_space_gamut_to_string(
+    enum_value: ColorSpaceGamut,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_color_space_transfer_function_to_string(
+    enum_value: ColorSpaceTransferFunction,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_enums_path_fill_type_to_string(
     enum_value: PathFillType,
@@ -202,3 +261,64 @@ pub extern "C" fn skia_placeholder_alignment_to_string(
         .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
         .log();
 }
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_scale_to_fit_to_string(
+    enum_value: ScaleToFit,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_region_op_to_string(
+    enum_value: RegionOp,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_image_bit_depth_to_string(
+    enum_value: BitDepth,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_typeface_serialize_behavior_to_string(
+    enum_value: SerializeBehavior,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_text_utils_align_to_string(
+    enum_value: TextUtilsAlign,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
+
+#[cfg(feature = "svg")]
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_enums_svg_visibility_to_string(
+    enum_value: SvgVisibility,
+    mut string: BorrowedPtr<StringBox>,
+) {
+    string
+        .with_mut_ok(|string| string.set_string(format!("{:?}", enum_value)))
+        .log();
+}
@@ -6,7 +6,7 @@ use compositor_skia::{Cache, SkiaCachelessCompositor, SkiaCompositor};
 use compositor_skia_platform::Platform;
 use fps_counter::FPSCounter;
 use lazy_static::lazy_static;
-use skia_safe::{Color, Color4f, Font, FontMgr, FontStyle, ISize, Paint, Point, Surface};
+use skia_safe::{Canvas, Color, Color4f, Font, FontMgr, FontStyle, ISize, Paint, Point, Surface};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 lazy_static! {
@@ -121,6 +121,24 @@ impl PlatformCompositor {
 
         Ok(())
     }
+
+    /// Composes the latest submitted layer on the given canvas without caching,
+    /// for example to record or inspect a frame. Can be called from any thread
+    pub fn compose_on(&self, canvas: &Canvas) -> Result<(), Box<dyn Error>> {
+        let current_layer = self
+            .latest_frame
+            .lock()
+            .map_err(|error| -> Box<dyn Error> {
+                format!("Failed to acquire Mutex lock: {}", error).into()
+            })?
+            .clone();
+
+        if let Some(layer) = current_layer {
+            SkiaCachelessCompositor::new(canvas).compose(layer);
+        }
+
+        Ok(())
+    }
 }
 
 pub enum PlatformContext {
@@ -227,6 +245,19 @@ pub extern "C" fn skia_platform_compositor_draw_cacheless(
         .log();
 }
 
+/// Composes the latest submitted layer on the canvas, for example to count its overdraw
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_platform_compositor_compose_on(
+    compositor: BorrowedPtr<PlatformCompositor>,
+    canvas: BorrowedPtr<Canvas>,
+) {
+    compositor
+        .with_ref(|compositor| {
+            canvas.with_ref(|canvas| compositor.compose_on(canvas).map_err(|error| error.into()))
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_platform_compositor_resize(
     mut compositor: BorrowedPtr<PlatformCompositor>,
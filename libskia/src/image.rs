@@ -3,15 +3,19 @@ use std::io::Read;
 use std::io::Write;
 
 use array_box::ArrayBox;
-use skia_safe::gpu::{BackendTexture, SurfaceOrigin};
+use skia_safe::gpu::{BackendTexture, Budgeted, DirectContext, SurfaceOrigin};
 use skia_safe::image::CachingHint;
+use skia_safe::images::BitDepth;
 use skia_safe::{
-    AlphaType, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, ISize, Image, ImageInfo,
-    M44, Paint, gpu, images, surfaces,
+    AlphaType, Bitmap, BlendMode, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, IRect,
+    ISize, Image, ImageFilter, ImageInfo, M44, Matrix, Paint, Picture, Pixmap, SamplingOptions,
+    gpu, images, surfaces,
 };
 use string_box::StringBox;
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
+use crate::pixmap::detach_pixmap;
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_image_from_pixels(
     pixels: BorrowedPtr<ArrayBox<u8>>,
@@ -41,6 +45,44 @@ pub extern "C" fn skia_image_from_pixels(
         .or_log(OwnedPtr::null())
 }
 
+/// Creates a lazy image of the given dimensions that draws the picture transformed by the matrix
+/// and with the paint the first time the image is drawn. Matrix, paint and color space may be null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_from_picture(
+    picture: BorrowedPtr<Picture>,
+    width: i32,
+    height: i32,
+    matrix: BorrowedPtr<Matrix>, // may be null
+    paint: BorrowedPtr<Paint>,   // may be null
+    bit_depth: BitDepth,
+    color_space: BorrowedPtr<ColorSpace>, // may be null
+) -> OwnedPtr<Image> {
+    picture
+        .with_clone(|picture| {
+            matrix.with_option_ref(|matrix| {
+                paint.with_option_ref(|paint| {
+                    color_space.with_option_ref(|color_space| {
+                        Ok(
+                            match images::deferred_from_picture(
+                                picture,
+                                ISize::new(width, height),
+                                matrix,
+                                paint,
+                                bit_depth,
+                                color_space.cloned(),
+                                None,
+                            ) {
+                                None => OwnedPtr::null(),
+                                Some(image) => OwnedPtr::new(image),
+                            },
+                        )
+                    })
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_image_from_file(boxer_string: BorrowedPtr<StringBox>) -> OwnedPtr<Image> {
     boxer_string
@@ -160,6 +202,281 @@ pub extern "C" fn skia_scale_image(
         .or_log(OwnedPtr::null())
 }
 
+/// Returns a new image that contains the `subset` of the given image.
+/// The context is only needed for texture backed images and may be null otherwise
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_make_subset(
+    image: BorrowedPtr<Image>,
+    mut context: BorrowedPtr<DirectContext>, // may be null
+    subset: BorrowedPtr<IRect>,
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            subset.with_ref(|subset| {
+                let subset_image = if context.is_null() {
+                    image.make_subset(None, subset)
+                } else {
+                    context.with_mut_ok(|context| image.make_subset(context, subset))?
+                };
+                Ok(subset_image
+                    .map(OwnedPtr::new)
+                    .unwrap_or_else(OwnedPtr::null))
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Converts the pixels of the image into the target color space.
+/// The context is only needed for texture backed images and may be null otherwise
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_make_color_space(
+    image: BorrowedPtr<Image>,
+    mut context: BorrowedPtr<DirectContext>, // may be null
+    color_space: BorrowedPtr<ColorSpace>,
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            color_space.with_clone(|color_space| {
+                let converted_image = if context.is_null() {
+                    image.make_color_space(None, color_space)
+                } else {
+                    context.with_mut_ok(|context| image.make_color_space(context, color_space))?
+                };
+                Ok(converted_image
+                    .map(OwnedPtr::new)
+                    .unwrap_or_else(OwnedPtr::null))
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns a copy of the image that is tagged with a different color space without converting the pixels
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_reinterpret_color_space(
+    image: BorrowedPtr<Image>,
+    color_space: BorrowedPtr<ColorSpace>,
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            color_space.with_clone_ok(|color_space| {
+                image
+                    .reinterpret_color_space(color_space)
+                    .map(OwnedPtr::new)
+                    .unwrap_or_else(OwnedPtr::null)
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Converts the pixels of the image into the given color type and color space.
+/// Unlike [`skia_scale_image`] the resulting image keeps the dimensions and the alpha type of the source.
+/// The context is only needed for texture backed images and may be null otherwise
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_make_color_type_and_color_space(
+    image: BorrowedPtr<Image>,
+    mut context: BorrowedPtr<DirectContext>, // may be null
+    color_type: ColorType,
+    color_space: BorrowedPtr<ColorSpace>, // may be null
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            color_space.with_option_ref(|color_space| {
+                let color_space = color_space.cloned();
+                let converted_image = if context.is_null() {
+                    make_color_type_and_color_space(image, None, color_type, color_space)
+                } else {
+                    context.with_mut_ok(|context| {
+                        make_color_type_and_color_space(
+                            image,
+                            Some(context),
+                            color_type,
+                            color_space,
+                        )
+                    })?
+                };
+                Ok(match converted_image {
+                    None => {
+                        if cfg!(debug_assertions) {
+                            eprintln!(
+                                "[skia_image_make_color_type_and_color_space] Could not convert image to color type: {:?}",
+                                color_type
+                            );
+                        };
+                        OwnedPtr::null()
+                    }
+                    Some(image) => OwnedPtr::new(image),
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// `SkImage::makeColorTypeAndColorSpace`, which is not bound by skia-safe.
+/// Texture backed images are drawn into a texture of the context, like Skia does,
+/// and the pixels of the others are read into a new raster image
+fn make_color_type_and_color_space(
+    image: &Image,
+    context: Option<&mut DirectContext>,
+    color_type: ColorType,
+    color_space: Option<ColorSpace>,
+) -> Option<Image> {
+    let image_info = image
+        .image_info()
+        .with_color_type(color_type)
+        .with_color_space(color_space);
+
+    if image.is_texture_backed() {
+        let mut surface = gpu::surfaces::render_target(
+            context?,
+            Budgeted::No,
+            &image_info,
+            None,
+            SurfaceOrigin::TopLeft,
+            None,
+            false,
+            None,
+        )?;
+        let mut paint = Paint::default();
+        paint.set_blend_mode(BlendMode::Src);
+        surface
+            .canvas()
+            .draw_image(image, IPoint::new(0, 0), Some(&paint));
+        return Some(surface.image_snapshot());
+    }
+
+    let mut bitmap = Bitmap::new();
+    if !bitmap.try_alloc_pixels_info(&image_info, None)
+        || !image.read_pixels_to_pixmap_with_context(
+            None,
+            bitmap.pixmap(),
+            IPoint::new(0, 0),
+            CachingHint::Allow,
+        )
+    {
+        return None;
+    }
+    bitmap.set_immutable();
+    Some(bitmap.as_image())
+}
+
+/// Returns a raster image, copying texture backed images into CPU memory or decoding lazy images.
+/// The context is only needed for texture backed images and may be null otherwise
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_make_raster_image(
+    image: BorrowedPtr<Image>,
+    mut context: BorrowedPtr<DirectContext>, // may be null
+    caching_hint: CachingHint,
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            let raster_image = if context.is_null() {
+                image.make_raster_image(None, caching_hint)
+            } else {
+                context.with_mut_ok(|context| image.make_raster_image(context, caching_hint))?
+            };
+            Ok(raster_image
+                .map(OwnedPtr::new)
+                .unwrap_or_else(OwnedPtr::null))
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns an image that is not backed by a GPU texture, raster and lazy images are returned as is.
+/// The context must be the one that owns the texture of a texture backed image
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_make_non_texture_image(
+    image: BorrowedPtr<Image>,
+    mut context: BorrowedPtr<DirectContext>, // may be null
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            let non_texture_image = if context.is_null() {
+                image.make_non_texture_image(None)
+            } else {
+                context.with_mut_ok(|context| image.make_non_texture_image(context))?
+            };
+            Ok(non_texture_image
+                .map(OwnedPtr::new)
+                .unwrap_or_else(OwnedPtr::null))
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Applies an image filter to the `subset` of the image. The bounds of the valid pixels of the
+/// filtered image are written into `out_subset` and its translation relative to the source into `offset`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_make_with_filter(
+    image: BorrowedPtr<Image>,
+    image_filter: BorrowedPtr<ImageFilter>,
+    subset: BorrowedPtr<IRect>,
+    clip_bounds: BorrowedPtr<IRect>,
+    mut out_subset: BorrowedPtr<IRect>,
+    mut offset: BorrowedPtr<IPoint>,
+) -> OwnedPtr<Image> {
+    image
+        .with_clone(|image| {
+            image_filter.with_ref(|image_filter| {
+                subset.with_ref(|subset| {
+                    clip_bounds.with_ref(|clip_bounds| {
+                        out_subset.with_mut(|out_subset| {
+                            offset.with_mut_ok(|offset| {
+                                match images::make_with_filter(
+                                    image,
+                                    image_filter,
+                                    subset,
+                                    clip_bounds,
+                                ) {
+                                    None => OwnedPtr::null(),
+                                    Some((filtered_image, filtered_subset, filtered_offset)) => {
+                                        *out_subset = filtered_subset;
+                                        *offset = filtered_offset;
+                                        OwnedPtr::new(filtered_image)
+                                    }
+                                }
+                            })
+                        })
+                    })
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Scales the image to the dimensions of the given image info, converting pixels to its color type
+/// and color space. Unlike [`skia_scale_image`] the sampling is explicit, which allows cubic resampling
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_scale_pixels(
+    image: BorrowedPtr<Image>,
+    image_info: BorrowedPtr<ImageInfo>,
+    sampling_options: BorrowedPtr<SamplingOptions>,
+) -> OwnedPtr<Image> {
+    image
+        .with_ref(|image| {
+            image_info.with_ref(|image_info| {
+                sampling_options.with_clone_ok(|sampling_options| {
+                    // the allocation fails instead of aborting when the pixels do not fit in memory
+                    let mut bitmap = Bitmap::new();
+                    let is_scaled = bitmap.try_alloc_pixels_info(image_info, None)
+                        && image.scale_pixels(bitmap.pixmap(), sampling_options, CachingHint::Allow);
+                    if !is_scaled {
+                        if cfg!(debug_assertions) {
+                            eprintln!(
+                                "[skia_image_scale_pixels] Could not scale image to width: {:?} height: {:?} color type: {:?}",
+                                image_info.width(),
+                                image_info.height(),
+                                image_info.color_type()
+                            );
+                        };
+                        return OwnedPtr::null();
+                    }
+                    bitmap.set_immutable();
+                    OwnedPtr::new(bitmap.as_image())
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_image_get_image_info(image: BorrowedPtr<Image>) -> OwnedPtr<ImageInfo> {
     image
@@ -275,6 +592,51 @@ pub extern "C" fn skia_image_read_all_pixels(
         .or_log(false)
 }
 
+/// Returns a pixmap with direct access to the pixels of a raster image or null
+/// if the image is texture backed or lazily generated.
+///
+/// # Safety
+///
+/// The returned [`Pixmap`] points to the pixels of `image` and must not outlive it.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_peek_pixels(image: BorrowedPtr<Image>) -> OwnedPtr<Pixmap<'static>> {
+    image
+        .with_ref_ok(|image| match image.peek_pixels() {
+            None => OwnedPtr::null(),
+            Some(pixmap) => OwnedPtr::new(unsafe { detach_pixmap(pixmap) }),
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Reads a rectangle of pixels starting at `src_x` and `src_y` into `pixels`.
+/// The size of the rectangle as well as the color type, alpha type and color space
+/// of the destination are taken from `image_info`, pixels are converted if needed.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_image_read_pixels(
+    image: BorrowedPtr<Image>,
+    src_x: i32,
+    src_y: i32,
+    image_info: BorrowedPtr<ImageInfo>,
+    mut pixels: BorrowedPtr<ArrayBox<u8>>,
+    row_bytes: usize,
+) -> bool {
+    image
+        .with_ref(|image| {
+            image_info.with_ref(|image_info| {
+                pixels.with_mut_ok(|pixels| {
+                    image.read_pixels(
+                        image_info,
+                        pixels.to_slice_mut(),
+                        row_bytes,
+                        IPoint::new(src_x, src_y),
+                        CachingHint::Disallow,
+                    )
+                })
+            })
+        })
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_image_drop(image: OwnedPtr<Image>) {
     drop(image);
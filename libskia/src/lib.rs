@@ -8,13 +8,18 @@ pub use compositor_skia_ffi::*;
 use log::warn;
 pub use value_box_ffi::*;
 
+pub mod bitmap;
+pub mod bounds_canvas;
 pub mod canvas;
 pub mod canvas_clip;
 pub mod canvas_draw_fill;
 pub mod canvas_draw_stroke;
 pub mod canvas_optimized;
 pub mod color;
+pub mod color4f;
 pub mod color_space;
+pub mod deferred_broadcast_canvas;
+pub mod drawable;
 pub mod enums;
 pub mod gpu;
 pub mod gradient;
@@ -22,20 +27,28 @@ pub mod image;
 pub mod image_filters;
 pub mod image_info;
 pub mod layer;
+pub mod m44;
 pub mod matrix;
+pub mod overdraw;
 pub mod paint;
 pub mod paragraph;
 pub mod path;
 pub mod path_effect;
 pub mod picture;
+pub mod picture_debugger;
+pub mod pixmap;
 pub mod recorder;
 pub mod rectangle;
+pub mod region;
 pub mod rounded_rectangle;
+pub mod sampling_options;
 pub mod shader;
 pub mod surface;
 pub mod surface_props;
 #[cfg(feature = "svg")]
 pub mod svg;
+#[cfg(feature = "svg")]
+pub mod svg_resources;
 pub mod text;
 pub mod types;
 
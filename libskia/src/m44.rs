@@ -0,0 +1,169 @@
+use array_box::ArrayBox;
+use skia_safe::{M44, Matrix, V3, V4, scalar};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_new_identity() -> OwnedPtr<M44> {
+    OwnedPtr::new(M44::new_identity())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_new_translate(x: scalar, y: scalar, z: scalar) -> OwnedPtr<M44> {
+    OwnedPtr::new(M44::translate(x, y, z))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_new_scale(x: scalar, y: scalar, z: scalar) -> OwnedPtr<M44> {
+    OwnedPtr::new(M44::scale(x, y, z))
+}
+
+/// Creates a matrix that rotates by `radians` around the axis, the axis does not have to be normalized.
+/// A zero length axis results in the identity matrix
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_new_rotate(
+    axis_x: scalar,
+    axis_y: scalar,
+    axis_z: scalar,
+    radians: scalar,
+) -> OwnedPtr<M44> {
+    OwnedPtr::new(M44::rotate(V3::new(axis_x, axis_y, axis_z), radians))
+}
+
+/// Creates a perspective projection with the given near and far planes and the field of view `angle` in radians
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_new_perspective(near: f32, far: f32, angle: f32) -> OwnedPtr<M44> {
+    OwnedPtr::new(M44::perspective(near, far, angle))
+}
+
+/// Creates a camera matrix looking from `eye` at `center` with the given `up` direction
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_new_look_at(
+    eye_x: scalar,
+    eye_y: scalar,
+    eye_z: scalar,
+    center_x: scalar,
+    center_y: scalar,
+    center_z: scalar,
+    up_x: scalar,
+    up_y: scalar,
+    up_z: scalar,
+) -> OwnedPtr<M44> {
+    OwnedPtr::new(M44::look_at(
+        &V3::new(eye_x, eye_y, eye_z),
+        &V3::new(center_x, center_y, center_z),
+        &V3::new(up_x, up_y, up_z),
+    ))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_from_matrix(matrix: BorrowedPtr<Matrix>) -> OwnedPtr<M44> {
+    matrix
+        .with_ref_ok(|matrix| OwnedPtr::new(M44::from(matrix)))
+        .or_log(OwnedPtr::null())
+}
+
+/// Drops the z row and column of the matrix
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_to_matrix(m44: BorrowedPtr<M44>) -> OwnedPtr<Matrix> {
+    m44.with_ref_ok(|m44| OwnedPtr::new(m44.to_m33()))
+        .or_log(OwnedPtr::null())
+}
+
+/// Writes the 16 members of the matrix in row-major order into `buffer`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_get_all(
+    m44: BorrowedPtr<M44>,
+    mut buffer: BorrowedPtr<ArrayBox<scalar>>,
+) {
+    m44.with_ref(|m44| {
+        buffer.with_mut_ok(|buffer| {
+            let mut members: [scalar; 16] = [0.0; 16];
+            m44.get_row_major(&mut members);
+            buffer.set_array(&members);
+        })
+    })
+    .log()
+}
+
+/// Sets the 16 members of the matrix from `buffer` in row-major order.
+/// Returns false if `buffer` does not contain exactly 16 members
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_set_all(
+    mut m44: BorrowedPtr<M44>,
+    buffer: BorrowedPtr<ArrayBox<scalar>>,
+) -> bool {
+    m44.with_mut(|m44| {
+        buffer.with_ref_ok(
+            |buffer| match <&[scalar; 16]>::try_from(buffer.to_slice()) {
+                Err(_) => false,
+                Ok(members) => {
+                    m44.set_row_major(members);
+                    true
+                }
+            },
+        )
+    })
+    .or_log(false)
+}
+
+/// Sets `m44` to `m44 * other`, so that `other` is applied first
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_pre_concat(mut m44: BorrowedPtr<M44>, other: BorrowedPtr<M44>) {
+    m44.with_mut(|m44| {
+        other.with_ref_ok(|other| {
+            m44.pre_concat(other);
+        })
+    })
+    .log();
+}
+
+/// Sets `m44` to `other * m44`, so that `other` is applied last
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_post_concat(mut m44: BorrowedPtr<M44>, other: BorrowedPtr<M44>) {
+    m44.with_mut(|m44| {
+        other.with_ref_ok(|other| {
+            m44.post_concat(other);
+        })
+    })
+    .log();
+}
+
+/// Writes the inverse of the matrix into `inverse`.
+/// Returns false and leaves `inverse` untouched if the matrix can not be inverted
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_invert(m44: BorrowedPtr<M44>, mut inverse: BorrowedPtr<M44>) -> bool {
+    m44.with_ref(|m44| {
+        inverse.with_mut_ok(|inverse| match m44.invert() {
+            None => false,
+            Some(inverted) => {
+                *inverse = inverted;
+                true
+            }
+        })
+    })
+    .or_log(false)
+}
+
+/// Multiplies the matrix with the column vector `x`, `y`, `z`, `w` and writes the resulting 4 components into `result`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_map(
+    m44: BorrowedPtr<M44>,
+    x: scalar,
+    y: scalar,
+    z: scalar,
+    w: scalar,
+    mut result: BorrowedPtr<ArrayBox<scalar>>,
+) {
+    m44.with_ref(|m44| {
+        result.with_mut_ok(|result| {
+            let V4 { x, y, z, w } = m44.map(x, y, z, w);
+            result.set_array(&[x, y, z, w]);
+        })
+    })
+    .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_m44_drop(m44: OwnedPtr<M44>) {
+    drop(m44);
+}
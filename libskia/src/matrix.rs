@@ -1,5 +1,6 @@
 use array_box::ArrayBox;
-use skia_safe::{Matrix, scalar};
+use skia_safe::matrix::ScaleToFit;
+use skia_safe::{Matrix, Point, Rect, scalar};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 #[unsafe(no_mangle)]
@@ -7,6 +8,51 @@ pub extern "C" fn skia_matrix_new_identity() -> OwnedPtr<Matrix> {
     OwnedPtr::new(Matrix::new_identity())
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_new_translate(dx: scalar, dy: scalar) -> OwnedPtr<Matrix> {
+    OwnedPtr::new(Matrix::translate((dx, dy)))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_new_scale(sx: scalar, sy: scalar) -> OwnedPtr<Matrix> {
+    OwnedPtr::new(Matrix::scale((sx, sy)))
+}
+
+/// Creates a matrix that rotates by `degrees` around the pivot point `px`, `py`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_new_rotate(
+    degrees: scalar,
+    px: scalar,
+    py: scalar,
+) -> OwnedPtr<Matrix> {
+    OwnedPtr::new(Matrix::rotate_deg_pivot(degrees, (px, py)))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_new_skew(kx: scalar, ky: scalar) -> OwnedPtr<Matrix> {
+    OwnedPtr::new(Matrix::skew((kx, ky)))
+}
+
+/// Creates a matrix that maps `source` onto `destination` according to `scale_to_fit`.
+/// Returns null if `source` is empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_new_rect_to_rect(
+    source: BorrowedPtr<Rect>,
+    destination: BorrowedPtr<Rect>,
+    scale_to_fit: ScaleToFit,
+) -> OwnedPtr<Matrix> {
+    source
+        .with_ref(|source| {
+            destination.with_ref_ok(|destination| {
+                match Matrix::from_rect_to_rect(source, destination, scale_to_fit) {
+                    None => OwnedPtr::null(),
+                    Some(matrix) => OwnedPtr::new(matrix),
+                }
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_matrix_get_all(
     matrix: BorrowedPtr<Matrix>,
@@ -45,6 +91,131 @@ pub extern "C" fn skia_matrix_set_all(
         .log();
 }
 
+/// Sets `matrix` to `matrix * other`, so that `other` is applied first
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_pre_concat(
+    mut matrix: BorrowedPtr<Matrix>,
+    other: BorrowedPtr<Matrix>,
+) {
+    matrix
+        .with_mut(|matrix| {
+            other.with_ref_ok(|other| {
+                matrix.pre_concat(other);
+            })
+        })
+        .log();
+}
+
+/// Sets `matrix` to `other * matrix`, so that `other` is applied last
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_post_concat(
+    mut matrix: BorrowedPtr<Matrix>,
+    other: BorrowedPtr<Matrix>,
+) {
+    matrix
+        .with_mut(|matrix| {
+            other.with_ref_ok(|other| {
+                matrix.post_concat(other);
+            })
+        })
+        .log();
+}
+
+/// Writes the inverse of the matrix into `inverse`.
+/// Returns false and leaves `inverse` untouched if the matrix can not be inverted
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_invert(
+    matrix: BorrowedPtr<Matrix>,
+    mut inverse: BorrowedPtr<Matrix>,
+) -> bool {
+    matrix
+        .with_ref(|matrix| {
+            inverse.with_mut_ok(|inverse| match matrix.invert() {
+                None => false,
+                Some(inverted) => {
+                    *inverse = inverted;
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+/// Maps the points in place
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_map_points(
+    matrix: BorrowedPtr<Matrix>,
+    mut points: BorrowedPtr<ArrayBox<Point>>,
+) {
+    matrix
+        .with_ref(|matrix| {
+            points.with_mut_ok(|points| {
+                let mut mapped = points.to_slice().to_vec();
+                matrix.map_points_inplace(&mut mapped);
+                points.set_array(&mapped);
+            })
+        })
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_map_point(
+    matrix: BorrowedPtr<Matrix>,
+    x: scalar,
+    y: scalar,
+) -> OwnedPtr<Point> {
+    matrix
+        .with_ref_ok(|matrix| OwnedPtr::new(matrix.map_xy(x, y)))
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the bounds of the mapped corners of `rect`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_map_rect(
+    matrix: BorrowedPtr<Matrix>,
+    rect: BorrowedPtr<Rect>,
+) -> OwnedPtr<Rect> {
+    matrix
+        .with_ref(|matrix| rect.with_ref_ok(|rect| OwnedPtr::new(matrix.map_rect(rect).0)))
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the mean radius of a circle after mapping, or 0 if the matrix has perspective
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_map_radius(matrix: BorrowedPtr<Matrix>, radius: scalar) -> scalar {
+    matrix
+        .with_ref_ok(|matrix| matrix.map_radius(radius).unwrap_or(0.0))
+        .or_log(0.0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_is_identity(matrix: BorrowedPtr<Matrix>) -> bool {
+    matrix
+        .with_ref_ok(|matrix| matrix.is_identity())
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_is_scale_translate(matrix: BorrowedPtr<Matrix>) -> bool {
+    matrix
+        .with_ref_ok(|matrix| matrix.is_scale_translate())
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_has_perspective(matrix: BorrowedPtr<Matrix>) -> bool {
+    matrix
+        .with_ref_ok(|matrix| matrix.has_perspective())
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_matrix_rect_stays_rect(matrix: BorrowedPtr<Matrix>) -> bool {
+    matrix
+        .with_ref_ok(|matrix| matrix.rect_stays_rect())
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_matrix_drop(matrix: OwnedPtr<Matrix>) {
     drop(matrix);
@@ -0,0 +1,240 @@
+use std::ptr::NonNull;
+
+use array_box::ArrayBox;
+use skia_safe::{
+    Canvas, Color, ColorFilter, Data, Image, ImageInfo, Paint, Picture, PictureRecorder, Rect,
+    images, surfaces,
+};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+use crate::picture_debugger::{is_overdraw_supported, overdraw_picture};
+
+/// Colors of the pixels drawn 0, 1, 2, 3, 4 and 5 or more times
+const DEFAULT_HEATMAP_COLORS: [Color; 6] = [
+    Color::TRANSPARENT,
+    Color::new(0x800000FF),
+    Color::new(0x8000FF00),
+    Color::new(0x80FF80C0),
+    Color::new(0x80FF0000),
+    Color::new(0xC0FF0000),
+];
+
+/// Counts how many times each pixel is touched by the commands drawn on its canvas in the alpha
+/// of a target canvas, like Skia's `SkOverdrawCanvas`. The draws are recorded and, when flushed,
+/// replayed on the target with paints that add 1/255 to the alpha of the pixels they cover.
+/// The target is usually the canvas of an `Alpha8` surface cleared to transparent.
+///
+/// skia-safe can not override the paints of a canvas, so the overdraw paints are written into
+/// the private serialized form of the recorded picture, see the picture debugger. If the linked
+/// Skia serializes pictures in a form that can not be rewritten, nothing is counted and every
+/// flush fails, see [`skia_overdraw_canvas_is_supported`]
+pub struct OverdrawCanvas {
+    recorder: PictureRecorder,
+    bounds: Rect,
+    target: NonNull<Canvas>,
+}
+
+impl OverdrawCanvas {
+    /// # Safety
+    ///
+    /// The target must stay valid until the overdraw canvas is dropped
+    pub unsafe fn new(target: &Canvas) -> Self {
+        let bounds = Rect::from_isize(target.base_layer_size());
+        let mut recorder = PictureRecorder::new();
+        recorder.begin_recording(bounds, false);
+        Self {
+            recorder,
+            bounds,
+            target: NonNull::from(target),
+        }
+    }
+
+    fn target(&self) -> &Canvas {
+        // the target is valid as required by `new`
+        unsafe { self.target.as_ref() }
+    }
+
+    pub fn canvas(&mut self) -> Option<&Canvas> {
+        self.recorder.recording_canvas()
+    }
+
+    /// Counts the draws of the picture on the target,
+    /// returns false if the picture could not be rewritten to count its draws
+    pub fn draw_picture(&self, picture: &Picture) -> bool {
+        match overdraw_picture(picture) {
+            None => false,
+            Some(overdraw) => {
+                overdraw.playback(self.target());
+                true
+            }
+        }
+    }
+
+    /// Counts what was drawn since the last flush on the target and starts over.
+    /// The transform and the clip of the canvas are reset
+    pub fn flush(&mut self) -> bool {
+        let counted = match self.recorder.finish_recording_as_picture(None) {
+            None => true,
+            Some(picture) => self.draw_picture(&picture),
+        };
+        self.recorder.begin_recording(self.bounds, false);
+        counted
+    }
+
+    /// Returns the amount of draws of each pixel of the target row by row, saturating at 255.
+    /// What was drawn on the canvas is flushed first,
+    /// none if the draws could not be counted or the target can not be read
+    pub fn counts(&mut self) -> Option<Vec<u8>> {
+        if !self.flush() {
+            return None;
+        }
+        let image_info = ImageInfo::new_a8(self.target().base_layer_size());
+        let mut counts = vec![0; image_info.compute_min_byte_size()];
+        self.target()
+            .read_pixels(&image_info, &mut counts, image_info.min_row_bytes(), (0, 0))
+            .then_some(counts)
+    }
+
+    /// Renders the counts as an image, coloring the pixels drawn `n` times with `colors[min(n, 5)]`
+    pub fn heatmap(&mut self, colors: &[Color; 6]) -> Option<Image> {
+        let size = self.target().base_layer_size();
+        let image_info = ImageInfo::new_a8(size);
+        let counts = images::raster_from_data(
+            &image_info,
+            Data::new_copy(&self.counts()?),
+            image_info.min_row_bytes(),
+        )?;
+
+        // the overdraw color filter picks the color based on the alpha of the counts
+        let mut paint = Paint::default();
+        paint.set_color_filter(ColorFilter::overdraw(colors));
+
+        let mut surface = surfaces::raster_n32_premul(size)?;
+        surface.canvas().draw_image(&counts, (0, 0), Some(&paint));
+        Some(surface.image_snapshot())
+    }
+}
+
+/// Returns true if the overdraw canvas can count draws with the linked Skia.
+/// Otherwise drawing pictures, flushing, the counts and the heatmap fail
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_is_supported() -> bool {
+    is_overdraw_supported()
+}
+
+/// Creates an overdraw canvas that counts the draws in the alpha of the `target`,
+/// usually the canvas of an `Alpha8` surface cleared to transparent.
+///
+/// # Safety
+///
+/// The `target` must not be dropped before the overdraw canvas.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_new(
+    target: BorrowedPtr<Canvas>,
+) -> OwnedPtr<OverdrawCanvas> {
+    target
+        .with_ref_ok(|target| OwnedPtr::new(unsafe { OverdrawCanvas::new(target) }))
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the canvas to draw on, for example by composing layers.
+///
+/// # Safety
+///
+/// The returned [`BorrowedPtr<Canvas>`] is borrowed from `overdraw_canvas`
+/// and must not outlive it or be used after the draws are flushed.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_get_canvas(
+    mut overdraw_canvas: BorrowedPtr<OverdrawCanvas>,
+) -> BorrowedPtr<Canvas> {
+    overdraw_canvas
+        .with_mut_ok(|overdraw_canvas| match overdraw_canvas.canvas() {
+            None => BorrowedPtr::null(),
+            Some(canvas) => BorrowedPtr::from_ref(canvas),
+        })
+        .or_log(BorrowedPtr::null())
+}
+
+/// Counts the draws of the picture on the target without recording them.
+/// Returns false if the draws of the picture could not be counted
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_draw_picture(
+    overdraw_canvas: BorrowedPtr<OverdrawCanvas>,
+    picture: BorrowedPtr<Picture>,
+) -> bool {
+    overdraw_canvas
+        .with_ref(|overdraw_canvas| {
+            picture.with_ref_ok(|picture| overdraw_canvas.draw_picture(picture))
+        })
+        .or_log(false)
+}
+
+/// Counts everything drawn on the canvas since the last flush on the target.
+/// Returns false if the draws could not be counted
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_flush(
+    mut overdraw_canvas: BorrowedPtr<OverdrawCanvas>,
+) -> bool {
+    overdraw_canvas
+        .with_mut_ok(|overdraw_canvas| overdraw_canvas.flush())
+        .or_log(false)
+}
+
+/// Flushes the canvas and writes the amount of draws of each pixel row by row.
+/// Returns false if the draws could not be counted or the target can not be read
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_get_counts(
+    mut overdraw_canvas: BorrowedPtr<OverdrawCanvas>,
+    mut counts: BorrowedPtr<ArrayBox<u8>>,
+) -> bool {
+    overdraw_canvas
+        .with_mut(|overdraw_canvas| {
+            counts.with_mut_ok(|counts| match overdraw_canvas.counts() {
+                None => false,
+                Some(pixels) => {
+                    counts.set_vector(pixels);
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+/// Flushes the canvas and returns the heatmap of the counts, `colors` are the 6 colors of
+/// the pixels drawn 0, 1, 2, 3, 4 and 5 or more times. The default colors are used if `colors` is null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_make_heatmap(
+    mut overdraw_canvas: BorrowedPtr<OverdrawCanvas>,
+    colors: BorrowedPtr<ArrayBox<Color>>, // may be null
+) -> OwnedPtr<Image> {
+    overdraw_canvas
+        .with_mut(|overdraw_canvas| {
+            colors.with_option_ref(|colors| {
+                let colors = match colors {
+                    None => DEFAULT_HEATMAP_COLORS,
+                    Some(colors) => match colors.to_slice().try_into() {
+                        Ok(colors) => colors,
+                        Err(_) => {
+                            if cfg!(debug_assertions) {
+                                eprintln!(
+                                    "[skia_overdraw_canvas_make_heatmap] Expected 6 colors, got: {:?}",
+                                    colors.length
+                                );
+                            };
+                            return Ok(OwnedPtr::null());
+                        }
+                    },
+                };
+                Ok(match overdraw_canvas.heatmap(&colors) {
+                    None => OwnedPtr::null(),
+                    Some(image) => OwnedPtr::new(image),
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_overdraw_canvas_drop(overdraw_canvas: OwnedPtr<OverdrawCanvas>) {
+    drop(overdraw_canvas);
+}
@@ -1,5 +1,7 @@
 use skia_safe::paint::{Cap, Join, Style};
-use skia_safe::{BlendMode, Color, ImageFilter, Paint, PathEffect, Shader, scalar};
+use skia_safe::{
+    BlendMode, Color, Color4f, ColorSpace, ImageFilter, Paint, PathEffect, Shader, scalar,
+};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 #[unsafe(no_mangle)]
@@ -104,6 +106,32 @@ pub extern "C" fn skia_paint_get_color(paint: BorrowedPtr<Paint>) -> OwnedPtr<Co
         .or_log(OwnedPtr::null())
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_paint_get_color4f(paint: BorrowedPtr<Paint>) -> OwnedPtr<Color4f> {
+    paint
+        .with_ref_ok(|paint| OwnedPtr::new(paint.color4f()))
+        .or_log(OwnedPtr::null())
+}
+
+/// Sets the color of the paint, the color is interpreted in `color_space` or in sRGB if it is null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_paint_set_color4f(
+    mut paint: BorrowedPtr<Paint>,
+    color: BorrowedPtr<Color4f>,
+    color_space: BorrowedPtr<ColorSpace>, // may be null
+) {
+    paint
+        .with_mut(|paint| {
+            color.with_ref(|color| {
+                color_space.with_option_ref(|color_space| {
+                    paint.set_color4f(color, color_space);
+                    Ok(())
+                })
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_paint_get_stroke_width(paint: BorrowedPtr<Paint>) -> scalar {
     paint.with_ref_ok(|paint| paint.stroke_width()).or_log(0.0)
@@ -1,7 +1,30 @@
 use array_box::ArrayBox;
-use skia_safe::{Canvas, Picture, Rect};
+use skia_safe::{
+    Canvas, Color, Drawable, IRect, Image, Matrix, Picture, Rect, RoundOut, scalar, surfaces,
+};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
+/// Creates a picture from data written by [`skia_picture_serialize`], returns null if the data is invalid.
+/// Pictures should only be deserialized from trusted sources
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_deserialize(data: BorrowedPtr<ArrayBox<u8>>) -> OwnedPtr<Picture> {
+    data.with_ref_ok(|data| match Picture::from_bytes(data.to_slice()) {
+        None => OwnedPtr::null(),
+        Some(picture) => OwnedPtr::new(picture),
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Returns a picture with the current content of the drawable
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_make_from_drawable(
+    mut drawable: BorrowedPtr<Drawable>,
+) -> OwnedPtr<Picture> {
+    drawable
+        .with_mut_ok(|drawable| OwnedPtr::new(drawable.make_picture_snapshot()))
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_picture_cull_rect(picture: BorrowedPtr<Picture>) -> OwnedPtr<Rect> {
     picture
@@ -23,6 +46,13 @@ pub extern "C" fn skia_picture_unique_id(picture: BorrowedPtr<Picture>) -> u32 {
         .or_log(0)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_approximate_bytes_used(picture: BorrowedPtr<Picture>) -> usize {
+    picture
+        .with_ref_ok(|picture| picture.approximate_bytes_used())
+        .or_log(0)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_picture_approximate_op_count(mut picture: BorrowedPtr<Picture>) -> usize {
     picture
@@ -46,6 +76,72 @@ pub extern "C" fn skia_picture_playback(
         .log()
 }
 
+/// Plays the picture back transformed by `matrix` and clipped to `clip`.
+/// The clip is in the coordinates of the canvas before `matrix` is applied.
+/// Both `matrix` and `clip` may be null. The canvas state is restored afterwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_playback_with_matrix_and_clip(
+    picture: BorrowedPtr<Picture>,
+    canvas: BorrowedPtr<Canvas>,
+    matrix: BorrowedPtr<Matrix>, // may be null
+    clip: BorrowedPtr<Rect>,     // may be null
+) {
+    picture
+        .with_ref(|picture| {
+            canvas.with_ref(|canvas| {
+                matrix.with_option_ref(|matrix| {
+                    clip.with_option_ref(|clip| {
+                        let save_count = canvas.save();
+                        if let Some(clip) = clip {
+                            canvas.clip_rect(clip, None, None);
+                        }
+                        if let Some(matrix) = matrix {
+                            canvas.concat(matrix);
+                        }
+                        picture.playback(canvas);
+                        canvas.restore_to_count(save_count);
+                        Ok(())
+                    })
+                })
+            })
+        })
+        .log();
+}
+
+/// Draws the picture scaled by `scale` over the background color into a raster image
+/// of the size of the scaled cull rect. Returns null if the scaled cull rect is empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_to_raster_image(
+    picture: BorrowedPtr<Picture>,
+    scale: scalar,
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+) -> OwnedPtr<Image> {
+    picture
+        .with_ref_ok(|picture| {
+            let cull_rect = picture.cull_rect();
+            let bounds: IRect = Rect::new(
+                cull_rect.left * scale,
+                cull_rect.top * scale,
+                cull_rect.right * scale,
+                cull_rect.bottom * scale,
+            )
+            .round_out();
+            let Some(mut surface) = surfaces::raster_n32_premul(bounds.size()) else {
+                return OwnedPtr::null();
+            };
+            let canvas = surface.canvas();
+            canvas.clear(Color::from_argb(a, r, g, b));
+            canvas.translate((-bounds.left as scalar, -bounds.top as scalar));
+            canvas.scale((scale, scale));
+            picture.playback(canvas);
+            OwnedPtr::new(surface.image_snapshot())
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_picture_serialize(
     mut picture: BorrowedPtr<Picture>,
@@ -0,0 +1,1330 @@
+use std::fmt::Write;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use log::error;
+use skia_safe::paint::Style;
+use skia_safe::{
+    Canvas, ClipOp, Color, IRect, ImageInfo, M44, Matrix, Paint, Picture, PictureRecorder, Rect,
+    scalar, surfaces,
+};
+use string_box::StringBox;
+use value_box::{BorrowedPtr, ReturnBoxerResult};
+
+// The inspector decodes the serialized form of a picture (the same op stream Skia's debugger
+// walks), because skia-safe does not allow to subclass a canvas to intercept the draw calls.
+// That form is private to Skia and changes between its versions, so skia-safe is pinned to
+// an exact version in Cargo.toml and the inspector first checks that it decodes a probe picture
+// recorded with known commands. If it does not, every function of the debugger fails.
+
+const PICTURE_MAGIC: &[u8; 8] = b"skiapict";
+
+/// The commands of the probe picture as the inspector must list them
+const PROBE_COMMANDS: [&str; 6] = [
+    "Save",
+    "Translate",
+    "ClipRect",
+    "DrawRect",
+    "DrawOval",
+    "Restore",
+];
+
+const READER_TAG: u32 = u32::from_be_bytes(*b"read");
+const FACTORY_TAG: u32 = u32::from_be_bytes(*b"fact");
+const TYPEFACE_TAG: u32 = u32::from_be_bytes(*b"tpfc");
+const PICTURE_TAG: u32 = u32::from_be_bytes(*b"pctr");
+const DRAWABLE_TAG: u32 = u32::from_be_bytes(*b"dral");
+const BUFFER_SIZE_TAG: u32 = u32::from_be_bytes(*b"aray");
+const PAINT_BUFFER_TAG: u32 = u32::from_be_bytes(*b"pnt ");
+const PATH_BUFFER_TAG: u32 = u32::from_be_bytes(*b"pth ");
+const TEXT_BLOB_BUFFER_TAG: u32 = u32::from_be_bytes(*b"blob");
+const EOF_TAG: u32 = u32::from_be_bytes(*b"eof ");
+
+/// Names of the draw types of the op stream, indexed by their value.
+/// The ops Skia no longer writes are marked as retired
+const COMMAND_NAMES: [&str; 77] = [
+    "Unused",
+    "ClipPath",
+    "ClipRegion",
+    "ClipRect",
+    "ClipRRect",
+    "Concat",
+    "DrawBitmap",
+    "DrawBitmapMatrix",
+    "DrawBitmapNine",
+    "DrawBitmapRect",
+    "DrawClear",
+    "DrawData",
+    "DrawOval",
+    "DrawPaint",
+    "DrawPath",
+    "DrawPicture",
+    "DrawPoints",
+    "DrawPosText",
+    "DrawPosTextTopBottom",
+    "DrawPosTextH",
+    "DrawPosTextHTopBottom",
+    "DrawRect",
+    "DrawRRect",
+    "DrawSprite",
+    "DrawText",
+    "DrawTextOnPath",
+    "DrawTextTopBottom",
+    "DrawVerticesRetired",
+    "Restore",
+    "Rotate",
+    "Save",
+    "SaveLayerSaveFlags",
+    "Scale",
+    "SetMatrix",
+    "Skew",
+    "Translate",
+    "Noop",
+    "BeginCommentGroup",
+    "Comment",
+    "EndCommentGroup",
+    "DrawDRRect",
+    "PushCull",
+    "PopCull",
+    "DrawPatch",
+    "DrawPictureMatrixPaint",
+    "DrawTextBlob",
+    "DrawImageRetired",
+    "DrawImageRectStrict",
+    "DrawAtlas",
+    "DrawImageNine",
+    "DrawImageRectRetired",
+    "SaveLayerSaveLayerFlags",
+    "SaveLayer",
+    "DrawAnnotation",
+    "DrawDrawable",
+    "DrawDrawableMatrix",
+    "DrawTextRSXform",
+    "TranslateZ",
+    "DrawShadowRec",
+    "DrawImageLatticeRetired",
+    "DrawArc",
+    "DrawRegion",
+    "DrawVertices",
+    "Flush",
+    "DrawEdgeAAImageSetRetired",
+    "SaveBehind",
+    "DrawEdgeAAQuad",
+    "DrawBehindPaint",
+    "Concat44",
+    "ClipShader",
+    "MarkCTM",
+    "SetM44",
+    "DrawImage",
+    "DrawImageRect",
+    "DrawImageLattice",
+    "DrawEdgeAAImageSet",
+    "ResetClip",
+];
+
+const CLIP_PATH: u32 = 1;
+const CLIP_REGION: u32 = 2;
+const CLIP_RECT: u32 = 3;
+const CLIP_RRECT: u32 = 4;
+const CONCAT: u32 = 5;
+const DRAW_OVAL: u32 = 12;
+const DRAW_PAINT: u32 = 13;
+const DRAW_PATH: u32 = 14;
+const DRAW_POINTS: u32 = 16;
+const DRAW_RECT: u32 = 21;
+const DRAW_RRECT: u32 = 22;
+const RESTORE: u32 = 28;
+const ROTATE: u32 = 29;
+const SAVE: u32 = 30;
+const SCALE: u32 = 32;
+const SET_MATRIX: u32 = 33;
+const SKEW: u32 = 34;
+const TRANSLATE: u32 = 35;
+const DRAW_DRRECT: u32 = 40;
+const DRAW_PATCH: u32 = 43;
+const DRAW_PICTURE_MATRIX_PAINT: u32 = 44;
+const DRAW_TEXT_BLOB: u32 = 45;
+const DRAW_ATLAS: u32 = 48;
+const SAVE_LAYER: u32 = 52;
+const DRAW_ANNOTATION: u32 = 53;
+const DRAW_ARC: u32 = 60;
+const DRAW_REGION: u32 = 61;
+const DRAW_VERTICES: u32 = 62;
+const SAVE_BEHIND: u32 = 65;
+const DRAW_EDGE_AA_QUAD: u32 = 66;
+const DRAW_BEHIND_PAINT: u32 = 67;
+const CONCAT_44: u32 = 68;
+const CLIP_SHADER: u32 = 69;
+const SET_M44: u32 = 71;
+const DRAW_IMAGE: u32 = 72;
+const DRAW_IMAGE_RECT: u32 = 73;
+const DRAW_IMAGE_LATTICE: u32 = 74;
+const DRAW_EDGE_AA_IMAGE_SET: u32 = 75;
+const RESET_CLIP: u32 = 76;
+
+const SAVE_LAYER_HAS_BOUNDS: u32 = 1 << 0;
+const SAVE_LAYER_HAS_PAINT: u32 = 1 << 1;
+const CLIP_OP_INTERSECT: u32 = 1;
+
+const PAINT_HAS_EFFECTS: u32 = 0x2;
+const BLEND_MODE_PLUS: u32 = 12;
+/// The stroke width and miter of a default paint
+const DEFAULT_PAINT_STROKE: [scalar; 2] = [0.0, 4.0];
+/// The alpha an overdraw paint adds to the pixels it covers
+const OVERDRAW_ALPHA: scalar = 1.0 / 255.0;
+const PAINT_EFFECTS: [&str; 6] = [
+    "pathEffect",
+    "shader",
+    "maskFilter",
+    "colorFilter",
+    "imageFilter",
+    "blender",
+];
+const PAINT_STYLES: [&str; 3] = ["Fill", "Stroke", "StrokeAndFill"];
+const BLEND_MODES: [&str; 29] = [
+    "Clear",
+    "Src",
+    "Dst",
+    "SrcOver",
+    "DstOver",
+    "SrcIn",
+    "DstIn",
+    "SrcOut",
+    "DstOut",
+    "SrcATop",
+    "DstATop",
+    "Xor",
+    "Plus",
+    "Modulate",
+    "Screen",
+    "Overlay",
+    "Darken",
+    "Lighten",
+    "ColorDodge",
+    "ColorBurn",
+    "HardLight",
+    "SoftLight",
+    "Difference",
+    "Exclusion",
+    "Multiply",
+    "Hue",
+    "Saturation",
+    "Color",
+    "Luminosity",
+];
+
+const RRECT_SIZE: usize = 52;
+const PATH_TYPE_GENERAL: u32 = 0;
+const PATH_TYPE_RRECT: u32 = 1;
+
+const FONT_FAMILY_NAME: usize = 0x01;
+const FONT_FULL_NAME: usize = 0x04;
+const FONT_POSTSCRIPT_NAME: usize = 0x06;
+const FONT_WEIGHT: usize = 0x10;
+const FONT_WIDTH: usize = 0x11;
+const FONT_SLANT: usize = 0x12;
+const FONT_ITALIC: usize = 0x13;
+const FONT_PALETTE_INDEX: usize = 0xF8;
+const FONT_PALETTE_ENTRY_OVERRIDES: usize = 0xF9;
+const FONT_VARIATION: usize = 0xFA;
+const FONT_FACTORY_ID: usize = 0xFC;
+const FONT_INDEX: usize = 0xFD;
+const FONT_SENTINEL: usize = 0xFF;
+
+const FONT_SIZE_IS_BYTE: u32 = 1 << 31;
+const FONT_HAS_SCALE_X: u32 = 1 << 30;
+const FONT_HAS_SKEW_X: u32 = 1 << 29;
+const FONT_HAS_TYPEFACE: u32 = 1 << 28;
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], offset: usize) -> Self {
+        Self { bytes, offset }
+    }
+
+    fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let end = self.offset.checked_add(length)?;
+        let bytes = self.bytes.get(self.offset..end)?;
+        self.offset = end;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, length: usize) -> Option<()> {
+        self.bytes(length).map(|_| ())
+    }
+
+    fn skip_padded(&mut self, length: usize) -> Option<()> {
+        self.skip(length.checked_next_multiple_of(4)?)
+    }
+
+    fn skip_byte_array(&mut self) -> Option<()> {
+        let length = self.length()?;
+        self.skip_padded(length)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.u32().map(|value| value as i32)
+    }
+
+    fn length(&mut self) -> Option<usize> {
+        self.u32().map(|value| value as usize)
+    }
+
+    fn scalar(&mut self) -> Option<scalar> {
+        self.u32().map(f32::from_bits)
+    }
+
+    fn packed_length(&mut self) -> Option<usize> {
+        match self.u8()? {
+            0xFE => self
+                .bytes(2)
+                .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize),
+            0xFF => self.length(),
+            length => Some(length as usize),
+        }
+    }
+
+    /// Skips a typeface serialized as a font descriptor, a list of tagged values
+    /// that ends with a sentinel, followed by the font data
+    fn skip_typeface(&mut self) -> Option<()> {
+        // the style
+        self.packed_length()?;
+        loop {
+            match self.packed_length()? {
+                FONT_SENTINEL => break,
+                FONT_FAMILY_NAME | FONT_FULL_NAME | FONT_POSTSCRIPT_NAME => {
+                    let length = self.packed_length()?;
+                    self.skip(length)?;
+                }
+                FONT_WEIGHT | FONT_WIDTH | FONT_SLANT | FONT_ITALIC => self.skip(4)?,
+                FONT_PALETTE_INDEX | FONT_FACTORY_ID | FONT_INDEX => {
+                    self.packed_length()?;
+                }
+                FONT_PALETTE_ENTRY_OVERRIDES => {
+                    for _ in 0..self.packed_length()? {
+                        // the index and the color
+                        self.packed_length()?;
+                        self.skip(4)?;
+                    }
+                }
+                FONT_VARIATION => {
+                    // the axis tags and values
+                    let count = self.packed_length()?;
+                    self.skip(count.checked_mul(8)?)?;
+                }
+                _ => return None,
+            }
+        }
+        let length = self.packed_length()?;
+        self.skip(length)
+    }
+
+    fn rect(&mut self) -> Option<Rect> {
+        Some(Rect::new(
+            self.scalar()?,
+            self.scalar()?,
+            self.scalar()?,
+            self.scalar()?,
+        ))
+    }
+
+    fn rrect_bounds(&mut self) -> Option<Rect> {
+        let rect = self.rect()?;
+        self.skip(RRECT_SIZE - 16)?;
+        Some(rect)
+    }
+
+    fn region_bounds(&mut self) -> Option<Rect> {
+        if self.i32()? < 0 {
+            return Some(Rect::new_empty());
+        }
+        let bounds = IRect::new(self.i32()?, self.i32()?, self.i32()?, self.i32()?);
+        Some(Rect::from_irect(bounds))
+    }
+
+    fn matrix(&mut self) -> Option<Matrix> {
+        let mut values = [0.0; 9];
+        for value in values.iter_mut() {
+            *value = self.scalar()?;
+        }
+        let mut matrix = Matrix::default();
+        matrix.set_9(&values);
+        Some(matrix)
+    }
+
+    fn m44(&mut self) -> Option<M44> {
+        let mut values = [0.0; 16];
+        for value in values.iter_mut() {
+            *value = self.scalar()?;
+        }
+        Some(M44::col_major(&values))
+    }
+
+    fn points_bounds(&mut self, count: usize) -> Option<Rect> {
+        let mut bounds: Option<Rect> = None;
+        for _ in 0..count {
+            let (x, y) = (self.scalar()?, self.scalar()?);
+            bounds = Some(match bounds {
+                None => Rect::new(x, y, x, y),
+                Some(bounds) => Rect::new(
+                    bounds.left.min(x),
+                    bounds.top.min(y),
+                    bounds.right.max(x),
+                    bounds.bottom.max(y),
+                ),
+            });
+        }
+        Some(bounds.unwrap_or_else(Rect::new_empty))
+    }
+}
+
+/// What the inspector shows of a paint used by a command
+struct PaintSummary {
+    offset: usize,
+    color: [scalar; 4],
+    packed: u32,
+    style: u32,
+    stroke_width: scalar,
+    blend_mode: u32,
+    anti_alias: bool,
+    effects: Vec<(&'static str, String)>,
+}
+
+impl PaintSummary {
+    fn read(reader: &mut Reader, factories: &[String]) -> Option<Self> {
+        let offset = reader.offset;
+        let stroke_width = reader.scalar()?;
+        let _stroke_miter = reader.scalar()?;
+        let color = [
+            reader.scalar()?,
+            reader.scalar()?,
+            reader.scalar()?,
+            reader.scalar()?,
+        ];
+        let packed = reader.u32()?;
+
+        let mut effects = vec![];
+        if (packed >> 24) & PAINT_HAS_EFFECTS != 0 {
+            for effect in PAINT_EFFECTS {
+                let factory = reader.length()?;
+                if factory != 0 {
+                    let size = reader.length()?;
+                    reader.skip(size)?;
+                    let name = factories.get(factory - 1).cloned().unwrap_or_default();
+                    effects.push((effect, name));
+                }
+            }
+        }
+
+        Some(Self {
+            offset,
+            color,
+            packed,
+            style: (packed >> 20) & 0x3,
+            stroke_width,
+            blend_mode: (packed >> 8) & 0xFF,
+            anti_alias: packed & 0x1 != 0,
+            effects,
+        })
+    }
+
+    /// Writes the paint `SkOverdrawCanvas` replaces this paint with,
+    /// it keeps only the style and the stroke
+    fn write_overdraw(&self, bytes: &[u8], out: &mut Vec<u8>) {
+        // the stroke width and miter
+        out.extend_from_slice(&bytes[self.offset..self.offset + 8]);
+        write_plus_paint(out, OVERDRAW_ALPHA, self.packed);
+    }
+
+    fn outset_bounds(&self, bounds: Rect) -> Rect {
+        if self.style == 0 {
+            return bounds;
+        }
+        let radius = (self.stroke_width / 2.0).max(0.5);
+        bounds.with_outset((radius, radius))
+    }
+
+    fn write_json(&self, json: &mut String) {
+        let [r, g, b, a] = self.color;
+        let _ = write!(
+            json,
+            "{{\"color\":[{},{},{},{}],\"style\":\"{}\",\"strokeWidth\":{},\"blendMode\":\"{}\",\"antiAlias\":{},\"effects\":{{",
+            json_number(r),
+            json_number(g),
+            json_number(b),
+            json_number(a),
+            PAINT_STYLES.get(self.style as usize).unwrap_or(&"Unknown"),
+            json_number(self.stroke_width),
+            BLEND_MODES
+                .get(self.blend_mode as usize)
+                .unwrap_or(&"Custom"),
+            self.anti_alias
+        );
+        for (index, (effect, name)) in self.effects.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(json, "\"{}\":\"{}\"", effect, json_escape(name));
+        }
+        json.push_str("}}");
+    }
+}
+
+/// Locations of the op stream and the tables referenced by the ops in a serialized picture
+struct PictureLayout {
+    start: usize,
+    version: u32,
+    cull_rect: Rect,
+    ops: Range<usize>,
+    buffer: Option<Range<usize>>,
+    /// The offset of the amount of paints and the end of the paints in the buffer
+    paint_count: Option<usize>,
+    paints_end: Option<usize>,
+    factories: Vec<String>,
+    paints: Vec<PaintSummary>,
+    paths: Vec<Option<Rect>>,
+    text_blobs: Vec<Rect>,
+    /// The pictures drawn by the picture, serialized after its tables
+    pictures: Vec<PictureLayout>,
+    /// The end of the serialized picture, none if its tables could not be read
+    end: Option<usize>,
+}
+
+impl PictureLayout {
+    /// Returns the version of the serialized picture at `start`
+    fn version(bytes: &[u8], start: usize) -> Option<u32> {
+        if bytes.get(start..start.checked_add(PICTURE_MAGIC.len())?)? != PICTURE_MAGIC {
+            return None;
+        }
+        Reader::new(bytes, start + PICTURE_MAGIC.len()).u32()
+    }
+
+    /// Reads the picture at `start` if it has the given version
+    fn parse(bytes: &[u8], start: usize, version: u32) -> Option<Self> {
+        if Self::version(bytes, start)? != version {
+            return None;
+        }
+        let mut reader = Reader::new(bytes, start + PICTURE_MAGIC.len() + 4);
+        let cull_rect = reader.rect()?;
+
+        let mut layout = Self {
+            start,
+            version,
+            cull_rect,
+            ops: reader.offset..reader.offset,
+            buffer: None,
+            paint_count: None,
+            paints_end: None,
+            factories: vec![],
+            paints: vec![],
+            paths: vec![],
+            text_blobs: vec![],
+            pictures: vec![],
+            end: None,
+        };
+
+        // pictures without data have no commands
+        if reader.u8()? == 0 {
+            layout.end = Some(reader.offset);
+            return Some(layout);
+        }
+        if reader.u32()? != READER_TAG {
+            return None;
+        }
+        let ops_size = reader.length()?;
+        let ops_start = reader.offset;
+        reader.skip(ops_size)?;
+        layout.ops = ops_start..reader.offset;
+
+        // the tables are optional for the inspection, a command that refers
+        // to a table that could not be read simply has no paint or bounds
+        layout.read_tables(bytes, reader);
+
+        Some(layout)
+    }
+
+    fn read_tables(&mut self, bytes: &[u8], mut reader: Reader) -> Option<()> {
+        loop {
+            match reader.u32()? {
+                FACTORY_TAG => {
+                    let size = reader.length()?;
+                    self.read_factories(Reader::new(bytes, reader.offset));
+                    reader.skip(size)?;
+                }
+                TYPEFACE_TAG => {
+                    let count = reader.length()?;
+                    for _ in 0..count {
+                        reader.skip_typeface()?;
+                    }
+                }
+                BUFFER_SIZE_TAG => {
+                    let size = reader.length()?;
+                    let buffer = reader.offset..reader.offset.checked_add(size)?;
+                    reader.skip(size)?;
+                    self.buffer = Some(buffer.clone());
+                    self.read_buffer(Reader::new(bytes.get(..buffer.end)?, buffer.start));
+                }
+                PICTURE_TAG | DRAWABLE_TAG => {
+                    let count = reader.length()?;
+                    for _ in 0..count {
+                        let picture = Self::parse(bytes, reader.offset, self.version)?;
+                        reader.offset = picture.end?;
+                        self.pictures.push(picture);
+                    }
+                }
+                EOF_TAG => {
+                    self.end = Some(reader.offset);
+                    return Some(());
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn read_factories(&mut self, mut reader: Reader) -> Option<()> {
+        let count = reader.length()?;
+        for _ in 0..count {
+            let length = reader.packed_length()?;
+            let name = reader.bytes(length)?;
+            self.factories
+                .push(String::from_utf8_lossy(name).into_owned());
+        }
+        Some(())
+    }
+
+    fn read_buffer(&mut self, mut reader: Reader) -> Option<()> {
+        loop {
+            let tag = reader.u32()?;
+            let count = reader.length()?;
+            match tag {
+                PAINT_BUFFER_TAG => {
+                    self.paint_count = Some(reader.offset - 4);
+                    for _ in 0..count {
+                        let paint = PaintSummary::read(&mut reader, &self.factories)?;
+                        self.paints.push(paint);
+                    }
+                    self.paints_end = Some(reader.offset);
+                }
+                PATH_BUFFER_TAG => {
+                    let count = reader.length()?;
+                    for _ in 0..count {
+                        let bounds = read_path_bounds(&mut reader)?;
+                        self.paths.push(bounds);
+                    }
+                }
+                TEXT_BLOB_BUFFER_TAG => {
+                    for _ in 0..count {
+                        let bounds = read_text_blob_bounds(&mut reader)?;
+                        self.text_blobs.push(bounds);
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn paint(&self, index: u32) -> Option<&PaintSummary> {
+        // the indices are 1-based, 0 stands for no paint
+        self.paints.get((index as usize).checked_sub(1)?)
+    }
+
+    fn path_bounds(&self, index: u32) -> Option<Rect> {
+        *self.paths.get((index as usize).checked_sub(1)?)?
+    }
+
+    fn text_blob_bounds(&self, index: u32) -> Option<Rect> {
+        self.text_blobs
+            .get((index as usize).checked_sub(1)?)
+            .copied()
+    }
+
+    /// Decodes the op stream into commands, tracking the transform and the clip
+    fn commands(&self, bytes: &[u8]) -> Vec<Command> {
+        let mut state = CanvasState {
+            matrix: Matrix::default(),
+            clip: self.cull_rect,
+        };
+        let mut saved_states = vec![];
+        let mut commands = vec![];
+
+        let ops = &bytes[..self.ops.end];
+        let mut offset = self.ops.start;
+        while offset < self.ops.end {
+            let mut reader = Reader::new(ops, offset);
+            let Some(header) = reader.u32() else {
+                break;
+            };
+            let draw_type = header >> 24;
+            let mut size = (header & 0xFF_FFFF) as usize;
+            if size == 0xFF_FFFF {
+                let Some(extended_size) = reader.length() else {
+                    break;
+                };
+                size = extended_size;
+            }
+            let end = offset.saturating_add(size);
+            if size == 0 || end > self.ops.end {
+                break;
+            }
+
+            let mut command = Command {
+                draw_type,
+                name: COMMAND_NAMES
+                    .get(draw_type as usize)
+                    .copied()
+                    .unwrap_or("Unknown"),
+                ops: offset..end,
+                bounds: None,
+                matrix: Matrix::default(),
+                clip: Rect::new_empty(),
+                paint: None,
+                paint_offset: None,
+                restore_offset: None,
+            };
+            let mut reader = Reader::new(&ops[..end], reader.offset);
+            self.apply(
+                draw_type,
+                &mut reader,
+                &mut state,
+                &mut saved_states,
+                &mut command,
+            );
+            command.matrix = state.matrix;
+            command.clip = state.clip;
+            commands.push(command);
+
+            offset = end;
+        }
+        commands
+    }
+
+    fn apply(
+        &self,
+        draw_type: u32,
+        reader: &mut Reader,
+        state: &mut CanvasState,
+        saved_states: &mut Vec<CanvasState>,
+        command: &mut Command,
+    ) -> Option<()> {
+        match draw_type {
+            SAVE => saved_states.push(state.clone()),
+            SAVE_LAYER | SAVE_BEHIND => {
+                saved_states.push(state.clone());
+                let flags = reader.u32()?;
+                if flags & SAVE_LAYER_HAS_BOUNDS != 0 {
+                    command.bounds = Some(state.map_rect(reader.rect()?));
+                }
+                if draw_type == SAVE_LAYER && flags & SAVE_LAYER_HAS_PAINT != 0 {
+                    command.read_paint(reader)?;
+                }
+            }
+            RESTORE => {
+                if let Some(saved_state) = saved_states.pop() {
+                    *state = saved_state;
+                }
+            }
+            TRANSLATE => {
+                state
+                    .matrix
+                    .pre_translate((reader.scalar()?, reader.scalar()?));
+            }
+            SCALE => {
+                state
+                    .matrix
+                    .pre_scale((reader.scalar()?, reader.scalar()?), None);
+            }
+            ROTATE => {
+                state.matrix.pre_rotate(reader.scalar()?, None);
+            }
+            SKEW => {
+                state
+                    .matrix
+                    .pre_skew((reader.scalar()?, reader.scalar()?), None);
+            }
+            CONCAT => {
+                state.matrix.pre_concat(&reader.matrix()?);
+            }
+            SET_MATRIX => state.matrix = reader.matrix()?,
+            CONCAT_44 => {
+                state.matrix.pre_concat(&reader.m44()?.to_m33());
+            }
+            SET_M44 => state.matrix = reader.m44()?.to_m33(),
+            CLIP_RECT | CLIP_RRECT | CLIP_PATH | CLIP_REGION => {
+                let bounds = match draw_type {
+                    CLIP_RECT => Some(state.map_rect(reader.rect()?)),
+                    CLIP_RRECT => Some(state.map_rect(reader.rrect_bounds()?)),
+                    // regions are already in device coordinates
+                    CLIP_REGION => Some(reader.region_bounds()?),
+                    _ => self
+                        .path_bounds(reader.u32()?)
+                        .map(|bounds| state.map_rect(bounds)),
+                };
+                let clip_op = reader.u32()? & 0xF;
+                // the clips inside of a save are followed by the offset of the matching restore
+                if reader.offset + 4 == reader.bytes.len() {
+                    command.restore_offset = Some(reader.offset);
+                }
+                if let Some(bounds) = bounds {
+                    command.bounds = Some(bounds);
+                    if clip_op == CLIP_OP_INTERSECT && !state.clip.intersect(bounds) {
+                        state.clip = Rect::new_empty();
+                    }
+                }
+            }
+            CLIP_SHADER => {
+                command.read_paint(reader)?;
+            }
+            RESET_CLIP => state.clip = self.cull_rect,
+            DRAW_PAINT | DRAW_BEHIND_PAINT => {
+                command.read_paint(reader)?;
+                command.bounds = Some(state.clip);
+            }
+            DRAW_RECT | DRAW_OVAL | DRAW_ARC | DRAW_RRECT | DRAW_DRRECT | DRAW_REGION
+            | DRAW_PATH | DRAW_POINTS | DRAW_TEXT_BLOB | DRAW_IMAGE_RECT => {
+                let paint = command.read_paint(reader)?;
+                let bounds = match draw_type {
+                    DRAW_RECT | DRAW_OVAL | DRAW_ARC => Some(reader.rect()?),
+                    DRAW_RRECT | DRAW_DRRECT => Some(reader.rrect_bounds()?),
+                    DRAW_REGION => Some(reader.region_bounds()?),
+                    DRAW_PATH => self.path_bounds(reader.u32()?),
+                    DRAW_POINTS => {
+                        let _mode = reader.u32()?;
+                        let count = reader.length()?;
+                        Some(reader.points_bounds(count)?)
+                    }
+                    DRAW_TEXT_BLOB => {
+                        let text_blob = reader.u32()?;
+                        let (x, y) = (reader.scalar()?, reader.scalar()?);
+                        self.text_blob_bounds(text_blob)
+                            .map(|bounds| bounds.with_offset((x, y)))
+                    }
+                    _ => {
+                        let _image = reader.u32()?;
+                        let _src = reader.rect()?;
+                        Some(reader.rect()?)
+                    }
+                };
+                command.bounds = bounds.map(|bounds| {
+                    let bounds = match self.paint(paint) {
+                        None => bounds,
+                        Some(paint) => paint.outset_bounds(bounds),
+                    };
+                    state.map_rect(bounds)
+                });
+            }
+            DRAW_IMAGE
+            | DRAW_IMAGE_LATTICE
+            | DRAW_VERTICES
+            | DRAW_ATLAS
+            | DRAW_PATCH
+            | DRAW_EDGE_AA_IMAGE_SET
+            | DRAW_PICTURE_MATRIX_PAINT => {
+                command.read_paint(reader)?;
+            }
+            DRAW_ANNOTATION | DRAW_EDGE_AA_QUAD => {
+                command.bounds = Some(state.map_rect(reader.rect()?));
+            }
+            _ => {}
+        }
+        Some(())
+    }
+
+    /// Writes the picture with the paints of the draws and the layers replaced by overdraw paints,
+    /// see [`overdraw_picture`]
+    fn write_overdraw(&self, bytes: &[u8], out: &mut Vec<u8>) -> Option<()> {
+        let end = self.end?;
+        let Some(buffer) = self.buffer.clone() else {
+            // pictures without data draw nothing
+            return self
+                .ops
+                .is_empty()
+                .then(|| out.extend_from_slice(&bytes[self.start..end]));
+        };
+        // the draws can not be rewritten without all of the paints
+        if self.paint_count.is_some() && self.paints_end.is_none() {
+            return None;
+        }
+
+        // each paint gets an overdraw copy, followed by the paint of the draws without a paint
+        // and the paint of the layers, that adds the counts of the layer
+        let paint_count = self.paints.len() as u32;
+        let fill_paint = 2 * paint_count + 1;
+        let layer_paint = 2 * paint_count + 2;
+
+        let mut ops = bytes[self.start..buffer.start - 4].to_vec();
+        for command in self.commands(bytes) {
+            let (Some(paint), Some(offset)) = (command.paint, command.paint_offset) else {
+                continue;
+            };
+            let overdraw_paint = match command.draw_type {
+                // the pictures drawn without a paint are not drawn in a layer
+                SAVE_LAYER | DRAW_PICTURE_MATRIX_PAINT if paint != 0 => layer_paint,
+                SAVE_LAYER | DRAW_PICTURE_MATRIX_PAINT | CLIP_SHADER => continue,
+                _ if paint == 0 => fill_paint,
+                _ => paint + paint_count,
+            };
+            let offset = offset - self.start;
+            ops[offset..offset + 4].copy_from_slice(&overdraw_paint.to_le_bytes());
+        }
+
+        let mut paints = vec![];
+        for paint in &self.paints {
+            paint.write_overdraw(bytes, &mut paints);
+        }
+        for alpha in [OVERDRAW_ALPHA, 1.0] {
+            for value in DEFAULT_PAINT_STROKE {
+                paints.extend_from_slice(&value.to_le_bytes());
+            }
+            write_plus_paint(&mut paints, alpha, 0);
+        }
+
+        let mut tables = bytes[buffer.clone()].to_vec();
+        match (self.paint_count, self.paints_end) {
+            (Some(count), Some(paints_end)) => {
+                let count = count - buffer.start;
+                tables[count..count + 4].copy_from_slice(&(2 * paint_count + 2).to_le_bytes());
+                let paints_end = paints_end - buffer.start;
+                tables.splice(paints_end..paints_end, paints);
+            }
+            _ => {
+                let mut paint_table = PAINT_BUFFER_TAG.to_le_bytes().to_vec();
+                paint_table.extend_from_slice(&2u32.to_le_bytes());
+                paint_table.extend_from_slice(&paints);
+                tables.splice(0..0, paint_table);
+            }
+        }
+
+        out.extend_from_slice(&ops);
+        out.extend_from_slice(&(tables.len() as u32).to_le_bytes());
+        out.extend_from_slice(&tables);
+
+        let mut offset = buffer.end;
+        for picture in &self.pictures {
+            out.extend_from_slice(&bytes[offset..picture.start]);
+            picture.write_overdraw(bytes, out)?;
+            offset = picture.end?;
+        }
+        out.extend_from_slice(&bytes[offset..end]);
+        Some(())
+    }
+}
+
+/// Writes the color and the flags of a paint without effects that adds its alpha to the pixels
+/// it covers, the antialiasing, dithering, cap, join and style are taken from `packed`
+fn write_plus_paint(out: &mut Vec<u8>, alpha: scalar, packed: u32) {
+    for channel in [0.0, 0.0, 0.0, alpha] {
+        out.extend_from_slice(&channel.to_le_bytes());
+    }
+    let packed = (packed & 0x00FF_00FF) | (BLEND_MODE_PLUS << 8);
+    out.extend_from_slice(&packed.to_le_bytes());
+}
+
+fn read_path_bounds(reader: &mut Reader) -> Option<Option<Rect>> {
+    let packed = reader.u32()?;
+    let version = packed & 0xFF;
+    if version != 4 && version != 5 {
+        return None;
+    }
+    match (packed >> 28) & 0xF {
+        PATH_TYPE_GENERAL => {
+            let points = reader.length()?;
+            let conics = reader.length()?;
+            let verbs = reader.length()?;
+            let bounds = reader.points_bounds(points)?;
+            reader.skip(conics.checked_mul(4)?)?;
+            reader.skip_padded(verbs)?;
+            Some(Some(bounds))
+        }
+        PATH_TYPE_RRECT => {
+            let bounds = reader.rrect_bounds()?;
+            // the start index
+            reader.skip(4)?;
+            Some(Some(bounds))
+        }
+        _ => None,
+    }
+}
+
+fn read_text_blob_bounds(reader: &mut Reader) -> Option<Rect> {
+    let bounds = reader.rect()?;
+    // the runs end with a run of 0 glyphs
+    while reader.u32()? != 0 {
+        let positioning_and_extended = reader.u32()?;
+        let extended = (positioning_and_extended >> 8) & 0xFF != 0;
+        if extended {
+            let _text_size = reader.u32()?;
+        }
+        // run offset
+        reader.skip(8)?;
+
+        let font = reader.u32()?;
+        for present in [
+            font & FONT_SIZE_IS_BYTE == 0,
+            font & FONT_HAS_SCALE_X != 0,
+            font & FONT_HAS_SKEW_X != 0,
+            font & FONT_HAS_TYPEFACE != 0,
+        ] {
+            if present {
+                reader.skip(4)?;
+            }
+        }
+
+        // glyphs and positions, followed by clusters and text for extended runs
+        reader.skip_byte_array()?;
+        reader.skip_byte_array()?;
+        if extended {
+            reader.skip_byte_array()?;
+            reader.skip_byte_array()?;
+        }
+    }
+    Some(bounds)
+}
+
+#[derive(Clone)]
+struct CanvasState {
+    matrix: Matrix,
+    clip: Rect,
+}
+
+impl CanvasState {
+    fn map_rect(&self, rect: Rect) -> Rect {
+        self.matrix.map_rect(rect).0
+    }
+}
+
+/// A single command of the op stream with the canvas state after it is executed.
+/// Bounds and clip are in device coordinates, the clip is conservative
+struct Command {
+    draw_type: u32,
+    name: &'static str,
+    ops: Range<usize>,
+    bounds: Option<Rect>,
+    matrix: Matrix,
+    clip: Rect,
+    paint: Option<u32>,
+    paint_offset: Option<usize>,
+    restore_offset: Option<usize>,
+}
+
+impl Command {
+    fn read_paint(&mut self, reader: &mut Reader) -> Option<u32> {
+        self.paint_offset = Some(reader.offset);
+        let paint = reader.u32()?;
+        self.paint = Some(paint);
+        Some(paint)
+    }
+
+    fn write_json(&self, index: usize, layout: &PictureLayout, json: &mut String) {
+        let _ = write!(
+            json,
+            "{{\"index\":{},\"command\":\"{}\",\"bounds\":",
+            index, self.name
+        );
+        match self.bounds {
+            None => json.push_str("null"),
+            Some(bounds) => write_json_rect(json, bounds),
+        }
+
+        json.push_str(",\"matrix\":[");
+        let mut matrix = [0.0; 9];
+        self.matrix.get_9(&mut matrix);
+        for (index, value) in matrix.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str(&json_number(*value));
+        }
+
+        json.push_str("],\"clip\":");
+        write_json_rect(json, self.clip);
+
+        json.push_str(",\"paint\":");
+        match self.paint.and_then(|paint| layout.paint(paint)) {
+            None => json.push_str("null"),
+            Some(paint) => paint.write_json(json),
+        }
+        json.push('}');
+    }
+}
+
+fn write_json_rect(json: &mut String, rect: Rect) {
+    let _ = write!(
+        json,
+        "[{},{},{},{}]",
+        json_number(rect.left),
+        json_number(rect.top),
+        json_number(rect.right),
+        json_number(rect.bottom)
+    );
+}
+
+fn json_number(value: scalar) -> String {
+    if value.is_finite() {
+        format!("{}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+fn json_escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for character in string.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", character as u32);
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Records the probe picture, a red rect and a blue stroked oval in a translated and clipped save
+fn record_probe() -> Option<Picture> {
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 40.0, 40.0), false);
+    let mut fill = Paint::default();
+    fill.set_color(Color::RED);
+    let mut stroke = Paint::default();
+    stroke.set_color(Color::BLUE);
+    stroke.set_style(Style::Stroke);
+    stroke.set_stroke_width(2.0);
+    canvas.save();
+    canvas.translate((10.0, 10.0));
+    canvas.clip_rect(Rect::new(0.0, 0.0, 20.0, 20.0), ClipOp::Intersect, false);
+    canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &fill);
+    canvas.draw_oval(Rect::new(5.0, 5.0, 15.0, 15.0), &stroke);
+    canvas.restore();
+    recorder.finish_recording_as_picture(None)
+}
+
+/// Returns the version of the probe picture if the inspector decodes its commands and paints
+fn probe_picture_version() -> Option<u32> {
+    let bytes = record_probe()?.serialize().as_bytes().to_vec();
+    let version = PictureLayout::version(&bytes, 0)?;
+    let layout = PictureLayout::parse(&bytes, 0, version)?;
+    let commands = layout.commands(&bytes);
+    if !commands
+        .iter()
+        .map(|command| command.name)
+        .eq(PROBE_COMMANDS)
+    {
+        return None;
+    }
+
+    let paint = |index: usize| commands[index].paint.and_then(|paint| layout.paint(paint));
+    let (fill, stroke) = (paint(3)?, paint(4)?);
+    let is_decoded = commands[3].bounds == Some(Rect::new(10.0, 10.0, 20.0, 20.0))
+        && fill.color == [1.0, 0.0, 0.0, 1.0]
+        && fill.style == 0
+        && fill.blend_mode == 3
+        && stroke.color == [0.0, 0.0, 1.0, 1.0]
+        && stroke.style == 1
+        && stroke.stroke_width == 2.0;
+    is_decoded.then_some(version)
+}
+
+/// The version of the pictures serialized by the linked Skia,
+/// none if the inspector does not decode them
+fn supported_picture_version() -> Option<u32> {
+    static VERSION: OnceLock<Option<u32>> = OnceLock::new();
+    *VERSION.get_or_init(|| {
+        let version = probe_picture_version();
+        if version.is_none() {
+            error!(
+                "[picture_debugger] The pictures serialized by the linked Skia can not be decoded, the picture debugger is disabled"
+            );
+        }
+        version
+    })
+}
+
+fn inspect(picture: &Picture) -> Option<(Vec<u8>, PictureLayout, Vec<Command>)> {
+    let version = supported_picture_version()?;
+    let bytes = picture.serialize().as_bytes().to_vec();
+    let layout = PictureLayout::parse(&bytes, 0, version)?;
+    let commands = layout.commands(&bytes);
+    Some((bytes, layout, commands))
+}
+
+/// Serializes a picture with an op stream that consists only of the given commands
+fn splice_commands<'a>(
+    bytes: &[u8],
+    layout: &PictureLayout,
+    commands: impl IntoIterator<Item = &'a Command>,
+) -> Vec<u8> {
+    let mut ops = vec![];
+    for command in commands {
+        let start = ops.len();
+        ops.extend_from_slice(&bytes[command.ops.clone()]);
+        // the offsets of the restores are not valid in the new op stream,
+        // 0 tells the playback to not skip to the restore when the clip is empty
+        if let Some(restore_offset) = command.restore_offset {
+            let offset = start + restore_offset - command.ops.start;
+            ops[offset..offset + 4].fill(0);
+        }
+    }
+
+    let mut spliced = bytes[..layout.ops.start - 4].to_vec();
+    spliced.extend_from_slice(&(ops.len() as u32).to_le_bytes());
+    spliced.extend_from_slice(&ops);
+    spliced.extend_from_slice(&bytes[layout.ops.end..]);
+    spliced
+}
+
+/// Serializes the picture so that every draw adds 1/255 to the alpha of the pixels it covers,
+/// like the paints of `SkOverdrawCanvas` the overdraw paints keep only the style and the stroke.
+/// Layers with a paint and the pictures drawn by the picture add the counts of their draws
+fn write_overdraw_picture(picture: &Picture, version: u32) -> Option<Picture> {
+    let bytes = picture.serialize().as_bytes().to_vec();
+    let layout = PictureLayout::parse(&bytes, 0, version)?;
+    let mut overdraw = vec![];
+    layout.write_overdraw(&bytes, &mut overdraw)?;
+    Picture::from_bytes(&overdraw)
+}
+
+/// Returns true if the overdraw of the probe picture counts its draws: 1 where only the rect
+/// is drawn, 2 where the oval crosses it and 0 outside of both
+fn probe_overdraw(version: u32) -> bool {
+    let Some(overdraw) = record_probe().and_then(|probe| write_overdraw_picture(&probe, version))
+    else {
+        return false;
+    };
+    let Some(mut surface) = surfaces::raster(&ImageInfo::new_a8((40, 40)), None, None) else {
+        return false;
+    };
+    surface.canvas().clear(Color::TRANSPARENT);
+    overdraw.playback(surface.canvas());
+    let Some(pixels) = surface.peek_pixels() else {
+        return false;
+    };
+    let count = |x, y| pixels.get_alpha_f((x, y)) * 255.0;
+    (count(12, 12) - 1.0).abs() < 0.5
+        && (count(16, 16) - 2.0).abs() < 0.5
+        && count(35, 5).abs() < 0.5
+}
+
+/// Returns true if the pictures serialized by the linked Skia can be rewritten to count
+/// their draws, which is checked once with the probe picture
+pub(crate) fn is_overdraw_supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        let is_supported = supported_picture_version().is_some_and(probe_overdraw);
+        if !is_supported {
+            error!(
+                "[overdraw_canvas] The pictures serialized by the linked Skia can not be rewritten to count overdraw, the overdraw canvas is disabled"
+            );
+        }
+        is_supported
+    })
+}
+
+/// Returns the picture that counts the draws of the given one, see [`write_overdraw_picture`].
+/// None if the pictures of the linked Skia can not be rewritten
+pub(crate) fn overdraw_picture(picture: &Picture) -> Option<Picture> {
+    if !is_overdraw_supported() {
+        return None;
+    }
+    write_overdraw_picture(picture, supported_picture_version()?)
+}
+
+/// Returns true if the debugger can inspect the pictures serialized by the linked Skia.
+/// Otherwise every function of the debugger fails
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_debugger_is_supported() -> bool {
+    supported_picture_version().is_some()
+}
+
+/// Returns the amount of commands listed by [`skia_picture_debugger_commands_to_json`],
+/// this includes the save, restore, transform and clip commands.
+/// Returns -1 if the picture can not be inspected
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_debugger_command_count(picture: BorrowedPtr<Picture>) -> isize {
+    picture
+        .with_ref_ok(|picture| match inspect(picture) {
+            None => -1,
+            Some((_, _, commands)) => commands.len() as isize,
+        })
+        .or_log(-1)
+}
+
+/// Lists the commands of the picture as a JSON object with the cull rect and an array of commands.
+/// Each command has its index, name, device bounds (null if unknown), the 3x3 transform (row major),
+/// the device bounds of the clip and a summary of its paint (null if it has no paint).
+/// The JSON is `null` if the picture can not be inspected
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_debugger_commands_to_json(
+    picture: BorrowedPtr<Picture>,
+    mut json: BorrowedPtr<StringBox>,
+) {
+    picture
+        .with_ref(|picture| {
+            json.with_mut_ok(|json| {
+                let mut string = String::new();
+                match inspect(picture) {
+                    None => string.push_str("null"),
+                    Some((_, layout, commands)) => {
+                        string.push_str("{\"cullRect\":");
+                        write_json_rect(&mut string, layout.cull_rect);
+                        string.push_str(",\"commands\":[");
+                        for (index, command) in commands.iter().enumerate() {
+                            if index > 0 {
+                                string.push(',');
+                            }
+                            command.write_json(index, &layout, &mut string);
+                        }
+                        string.push_str("]}");
+                    }
+                }
+                json.set_string(string);
+            })
+        })
+        .log();
+}
+
+/// Plays back the first `count` commands of the picture on the canvas,
+/// the whole picture is played back if `count` is not less than the amount of commands.
+/// Returns false and draws nothing if the picture can not be inspected
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_debugger_playback_to(
+    picture: BorrowedPtr<Picture>,
+    canvas: BorrowedPtr<Canvas>,
+    count: usize,
+) -> bool {
+    picture
+        .with_ref(|picture| {
+            canvas.with_ref_ok(|canvas| {
+                let Some((bytes, layout, commands)) = inspect(picture) else {
+                    return false;
+                };
+                if count >= commands.len() {
+                    picture.playback(canvas);
+                    return true;
+                }
+                let truncated = splice_commands(&bytes, &layout, &commands[..count]);
+                match Picture::from_bytes(&truncated) {
+                    None => false,
+                    Some(truncated) => {
+                        truncated.playback(canvas);
+                        true
+                    }
+                }
+            })
+        })
+        .or_log(false)
+}
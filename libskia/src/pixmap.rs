@@ -0,0 +1,166 @@
+use std::os::raw::c_void;
+
+use skia_safe::{
+    AlphaType, Color, Color4f, ColorType, IPoint, IRect, ImageInfo, Pixmap, SamplingOptions,
+};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+/// Detaches a pixmap from the lifetime of the owner of its pixels so that it can be passed over FFI.
+///
+/// # Safety
+///
+/// The caller must make sure that the returned [`Pixmap`] does not outlive the
+/// image, surface or bitmap whose pixels it points to.
+pub(crate) unsafe fn detach_pixmap(pixmap: Pixmap<'_>) -> Pixmap<'static> {
+    unsafe { std::mem::transmute::<Pixmap<'_>, Pixmap<'static>>(pixmap) }
+}
+
+pub(crate) fn pixmap_contains(pixmap: &Pixmap, x: i32, y: i32) -> bool {
+    !pixmap.addr().is_null() && x >= 0 && x < pixmap.width() && y >= 0 && y < pixmap.height()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_image_info(
+    pixmap: BorrowedPtr<Pixmap<'static>>,
+) -> OwnedPtr<ImageInfo> {
+    pixmap
+        .with_ref_ok(|pixmap| OwnedPtr::new(pixmap.info().clone()))
+        .or_log(OwnedPtr::new(ImageInfo::default()))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_width(pixmap: BorrowedPtr<Pixmap<'static>>) -> i32 {
+    pixmap.with_ref_ok(|pixmap| pixmap.width()).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_height(pixmap: BorrowedPtr<Pixmap<'static>>) -> i32 {
+    pixmap.with_ref_ok(|pixmap| pixmap.height()).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_row_bytes(pixmap: BorrowedPtr<Pixmap<'static>>) -> usize {
+    pixmap.with_ref_ok(|pixmap| pixmap.row_bytes()).or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_color_type(pixmap: BorrowedPtr<Pixmap<'static>>) -> ColorType {
+    pixmap
+        .with_ref_ok(|pixmap| pixmap.color_type())
+        .or_log(ColorType::Unknown)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_alpha_type(pixmap: BorrowedPtr<Pixmap<'static>>) -> AlphaType {
+    pixmap
+        .with_ref_ok(|pixmap| pixmap.alpha_type())
+        .or_log(AlphaType::Unknown)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_compute_byte_size(pixmap: BorrowedPtr<Pixmap<'static>>) -> usize {
+    pixmap
+        .with_ref_ok(|pixmap| pixmap.compute_byte_size())
+        .or_log(0)
+}
+
+/// Returns the address of the first pixel. The memory is owned by the image, surface
+/// or bitmap the pixmap was created from and is only valid as long as that owner is alive
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_pixels(pixmap: BorrowedPtr<Pixmap<'static>>) -> *mut c_void {
+    pixmap
+        .with_ref_ok(|pixmap| pixmap.writable_addr())
+        .or_log(std::ptr::null_mut())
+}
+
+/// Returns the color of the pixel at `x` and `y` or null if it is out of bounds
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_color(
+    pixmap: BorrowedPtr<Pixmap<'static>>,
+    x: i32,
+    y: i32,
+) -> OwnedPtr<Color> {
+    pixmap
+        .with_ref_ok(|pixmap| {
+            if pixmap_contains(pixmap, x, y) {
+                OwnedPtr::new(pixmap.get_color(IPoint::new(x, y)))
+            } else {
+                OwnedPtr::null()
+            }
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the unpremultiplied float color of the pixel at `x` and `y` or null if it is out of bounds
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_get_color4f(
+    pixmap: BorrowedPtr<Pixmap<'static>>,
+    x: i32,
+    y: i32,
+) -> OwnedPtr<Color4f> {
+    pixmap
+        .with_ref_ok(|pixmap| {
+            if pixmap_contains(pixmap, x, y) {
+                OwnedPtr::new(pixmap.get_color_4f(IPoint::new(x, y)))
+            } else {
+                OwnedPtr::null()
+            }
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Fills the `area` of the pixmap with a color, the whole pixmap is erased if `area` is null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_erase(
+    mut pixmap: BorrowedPtr<Pixmap<'static>>,
+    color: BorrowedPtr<Color>,
+    area: BorrowedPtr<IRect>, // may be null
+) -> bool {
+    pixmap
+        .with_mut(|pixmap| {
+            color.with_clone(|color| area.with_option_ref(|area| Ok(pixmap.erase(color, area))))
+        })
+        .or_log(false)
+}
+
+/// # Safety
+///
+/// The returned [`Pixmap`] shares pixels with `pixmap` and must not outlive
+/// the owner of those pixels.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_extract_subset(
+    pixmap: BorrowedPtr<Pixmap<'static>>,
+    area: BorrowedPtr<IRect>,
+) -> OwnedPtr<Pixmap<'static>> {
+    pixmap
+        .with_ref(|pixmap| {
+            area.with_ref_ok(|area| match pixmap.extract_subset(area) {
+                None => OwnedPtr::null(),
+                Some(subset) => OwnedPtr::new(subset),
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Scales and converts the pixels of `pixmap` to fit the destination pixmap
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_scale_pixels(
+    pixmap: BorrowedPtr<Pixmap<'static>>,
+    mut destination: BorrowedPtr<Pixmap<'static>>,
+    sampling_options: BorrowedPtr<SamplingOptions>,
+) -> bool {
+    pixmap
+        .with_ref(|pixmap| {
+            destination.with_mut(|destination| {
+                sampling_options.with_clone_ok(|sampling_options| {
+                    pixmap.scale_pixels(destination, sampling_options)
+                })
+            })
+        })
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_pixmap_drop(pixmap: OwnedPtr<Pixmap<'static>>) {
+    drop(pixmap);
+}
@@ -1,4 +1,4 @@
-use skia_safe::{Canvas, Picture, PictureRecorder, Rect, scalar};
+use skia_safe::{Canvas, Drawable, Picture, PictureRecorder, Rect, scalar};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 #[unsafe(no_mangle)]
@@ -28,6 +28,31 @@ pub extern "C" fn skia_picture_recorder_begin_recording(
         .or_log(BorrowedPtr::null())
 }
 
+/// Begins recording into a picture that keeps an R-tree of the bounds of its commands,
+/// so that playback into a clipped canvas skips the commands outside of the clip.
+///
+/// # Safety
+///
+/// The returned [`BorrowedPtr<Canvas>`] is borrowed from `picture_recorder`
+/// and must not outlive that `PictureRecorder` or be used after recording is
+/// finished.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_recorder_begin_recording_with_rtree(
+    mut picture_recorder: BorrowedPtr<PictureRecorder>,
+    left: scalar,
+    top: scalar,
+    right: scalar,
+    bottom: scalar,
+) -> BorrowedPtr<Canvas> {
+    picture_recorder
+        .with_mut_ok(|recorder| {
+            BorrowedPtr::from_ref(
+                recorder.begin_recording(Rect::new(left, top, right, bottom), true),
+            )
+        })
+        .or_log(BorrowedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_picture_recorder_finish_recording(
     mut picture_recorder: BorrowedPtr<PictureRecorder>,
@@ -42,6 +67,19 @@ pub extern "C" fn skia_picture_recorder_finish_recording(
         .or_log(OwnedPtr::null())
 }
 
+/// Finishes recording into a drawable that is played back each time it is drawn
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_picture_recorder_finish_recording_as_drawable(
+    mut picture_recorder: BorrowedPtr<PictureRecorder>,
+) -> OwnedPtr<Drawable> {
+    picture_recorder
+        .with_mut_ok(|recorder| match recorder.finish_recording_as_drawable() {
+            None => OwnedPtr::null(),
+            Some(drawable) => OwnedPtr::new(drawable),
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_picture_recorder_drop(picture_recorder: OwnedPtr<PictureRecorder>) {
     drop(picture_recorder);
@@ -1,4 +1,4 @@
-use skia_safe::{IRect, Rect, scalar};
+use skia_safe::{Contains, IPoint, IRect, Point, Rect, RoundOut, scalar};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 ///
@@ -53,6 +53,114 @@ pub extern "C" fn skia_rectangle_f32_bottom(rectangle: BorrowedPtr<Rect>) -> sca
         .or_log(0.0)
 }
 
+/// Sets the rectangle to its intersection with `other`.
+/// Returns false and leaves the rectangle unchanged if they do not intersect
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_intersect(
+    mut rectangle: BorrowedPtr<Rect>,
+    other: BorrowedPtr<Rect>,
+) -> bool {
+    rectangle
+        .with_mut(|rectangle| other.with_ref_ok(|other| rectangle.intersect(other)))
+        .or_log(false)
+}
+
+/// Sets the rectangle to the union of itself and `other`, empty rectangles are ignored
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_join(
+    mut rectangle: BorrowedPtr<Rect>,
+    other: BorrowedPtr<Rect>,
+) {
+    rectangle
+        .with_mut(|rectangle| other.with_ref_ok(|other| rectangle.join(other)))
+        .log();
+}
+
+/// Returns true if the point is inside the rectangle, the right and bottom edges are exclusive
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_contains_point(
+    rectangle: BorrowedPtr<Rect>,
+    x: scalar,
+    y: scalar,
+) -> bool {
+    rectangle
+        .with_ref_ok(|rectangle| rectangle.contains(Point::new(x, y)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_contains_rectangle(
+    rectangle: BorrowedPtr<Rect>,
+    other: BorrowedPtr<Rect>,
+) -> bool {
+    rectangle
+        .with_ref(|rectangle| other.with_ref_ok(|other| rectangle.contains(other)))
+        .or_log(false)
+}
+
+/// Moves the edges towards the center, negative values move them outwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_inset(
+    mut rectangle: BorrowedPtr<Rect>,
+    dx: scalar,
+    dy: scalar,
+) {
+    rectangle
+        .with_mut_ok(|rectangle| rectangle.inset((dx, dy)))
+        .log();
+}
+
+/// Moves the edges away from the center, negative values move them inwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_outset(
+    mut rectangle: BorrowedPtr<Rect>,
+    dx: scalar,
+    dy: scalar,
+) {
+    rectangle
+        .with_mut_ok(|rectangle| rectangle.outset((dx, dy)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_offset(
+    mut rectangle: BorrowedPtr<Rect>,
+    dx: scalar,
+    dy: scalar,
+) {
+    rectangle
+        .with_mut_ok(|rectangle| rectangle.offset((dx, dy)))
+        .log();
+}
+
+/// Returns the smallest integer rectangle that contains the rectangle
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_round_out(rectangle: BorrowedPtr<Rect>) -> OwnedPtr<IRect> {
+    rectangle
+        .with_ref_ok(|rectangle| OwnedPtr::new(rectangle.round_out()))
+        .or_log(OwnedPtr::null())
+}
+
+/// Swaps left and right, and top and bottom if they are in the wrong order
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_sort(mut rectangle: BorrowedPtr<Rect>) {
+    rectangle.with_mut_ok(|rectangle| rectangle.sort()).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_is_empty(rectangle: BorrowedPtr<Rect>) -> bool {
+    rectangle
+        .with_ref_ok(|rectangle| rectangle.is_empty())
+        .or_log(true)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_f32_is_finite(rectangle: BorrowedPtr<Rect>) -> bool {
+    rectangle
+        .with_ref_ok(|rectangle| rectangle.is_finite())
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_rectangle_f32_drop(rectangle: OwnedPtr<Rect>) {
     drop(rectangle);
@@ -108,6 +216,99 @@ pub extern "C" fn skia_rectangle_i32_bottom(rectangle: BorrowedPtr<IRect>) -> i3
         .or_log(0)
 }
 
+/// Sets the rectangle to its intersection with `other`.
+/// Returns false and leaves the rectangle unchanged if they do not intersect
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_intersect(
+    mut rectangle: BorrowedPtr<IRect>,
+    other: BorrowedPtr<IRect>,
+) -> bool {
+    rectangle
+        .with_mut(|rectangle| {
+            other.with_ref_ok(|other| match IRect::intersect(rectangle, other) {
+                None => false,
+                Some(intersection) => {
+                    *rectangle = intersection;
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+/// Sets the rectangle to the union of itself and `other`, empty rectangles are ignored
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_join(
+    mut rectangle: BorrowedPtr<IRect>,
+    other: BorrowedPtr<IRect>,
+) {
+    rectangle
+        .with_mut(|rectangle| {
+            other.with_ref_ok(|other| {
+                *rectangle = IRect::join(rectangle, other);
+            })
+        })
+        .log();
+}
+
+/// Returns true if the point is inside the rectangle, the right and bottom edges are exclusive
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_contains_point(
+    rectangle: BorrowedPtr<IRect>,
+    x: i32,
+    y: i32,
+) -> bool {
+    rectangle
+        .with_ref_ok(|rectangle| rectangle.contains(IPoint::new(x, y)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_contains_rectangle(
+    rectangle: BorrowedPtr<IRect>,
+    other: BorrowedPtr<IRect>,
+) -> bool {
+    rectangle
+        .with_ref(|rectangle| other.with_ref_ok(|other| rectangle.contains(other)))
+        .or_log(false)
+}
+
+/// Moves the edges towards the center, negative values move them outwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_inset(mut rectangle: BorrowedPtr<IRect>, dx: i32, dy: i32) {
+    rectangle
+        .with_mut_ok(|rectangle| rectangle.inset((dx, dy)))
+        .log();
+}
+
+/// Moves the edges away from the center, negative values move them inwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_outset(mut rectangle: BorrowedPtr<IRect>, dx: i32, dy: i32) {
+    rectangle
+        .with_mut_ok(|rectangle| rectangle.outset((dx, dy)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_offset(mut rectangle: BorrowedPtr<IRect>, dx: i32, dy: i32) {
+    rectangle
+        .with_mut_ok(|rectangle| rectangle.offset((dx, dy)))
+        .log();
+}
+
+/// Swaps left and right, and top and bottom if they are in the wrong order
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_sort(mut rectangle: BorrowedPtr<IRect>) {
+    rectangle.with_mut_ok(|rectangle| rectangle.sort()).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rectangle_i32_is_empty(rectangle: BorrowedPtr<IRect>) -> bool {
+    rectangle
+        .with_ref_ok(|rectangle| rectangle.is_empty())
+        .or_log(true)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_rectangle_i32_drop(irect: OwnedPtr<IRect>) {
     drop(irect);
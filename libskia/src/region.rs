@@ -0,0 +1,212 @@
+use array_box::ArrayBox;
+use skia_safe::region::{Iterator as RegionIterator, RegionOp};
+use skia_safe::{IPoint, IRect, Path, Region, RoundOut};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_new() -> OwnedPtr<Region> {
+    OwnedPtr::new(Region::new())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_new_rect(rect: BorrowedPtr<IRect>) -> OwnedPtr<Region> {
+    rect.with_ref_ok(|rect| OwnedPtr::new(Region::from_rect(rect)))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_clone(region: BorrowedPtr<Region>) -> OwnedPtr<Region> {
+    region.with_clone_ok(OwnedPtr::new).or_log(OwnedPtr::null())
+}
+
+/// Makes the region empty. Always returns false, like `SkRegion::setEmpty`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_set_empty(mut region: BorrowedPtr<Region>) -> bool {
+    region
+        .with_mut_ok(|region| region.set_empty())
+        .or_log(false)
+}
+
+/// Sets the region to the rectangle, returns false if the region becomes empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_set_rect(
+    mut region: BorrowedPtr<Region>,
+    rect: BorrowedPtr<IRect>,
+) -> bool {
+    region
+        .with_mut(|region| rect.with_ref_ok(|rect| region.set_rect(rect)))
+        .or_log(false)
+}
+
+/// Sets the region to the union of the rectangles, returns false if the region becomes empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_set_rects(
+    mut region: BorrowedPtr<Region>,
+    rects: BorrowedPtr<ArrayBox<IRect>>,
+) -> bool {
+    region
+        .with_mut(|region| rects.with_ref_ok(|rects| region.set_rects(rects.to_slice())))
+        .or_log(false)
+}
+
+/// Sets the region to the pixels covered by the path within `clip`.
+/// If `clip` is null the bounds of the path are used as the clip.
+/// Returns false if the region becomes empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_set_path(
+    mut region: BorrowedPtr<Region>,
+    path: BorrowedPtr<Path>,
+    clip: BorrowedPtr<Region>, // may be null
+) -> bool {
+    region
+        .with_mut(|region| {
+            path.with_ref(|path| {
+                clip.with_option_ref(|clip| {
+                    Ok(match clip {
+                        None => {
+                            region.set_path(path, &Region::from_rect(path.bounds().round_out()))
+                        }
+                        Some(clip) => region.set_path(path, clip),
+                    })
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Combines the region with the rectangle, returns false if the region becomes empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_op_rect(
+    mut region: BorrowedPtr<Region>,
+    rect: BorrowedPtr<IRect>,
+    op: RegionOp,
+) -> bool {
+    region
+        .with_mut(|region| rect.with_ref_ok(|rect| region.op_rect(rect, op)))
+        .or_log(false)
+}
+
+/// Combines the region with another region, returns false if the region becomes empty
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_op_region(
+    mut region: BorrowedPtr<Region>,
+    other: BorrowedPtr<Region>,
+    op: RegionOp,
+) -> bool {
+    region
+        .with_mut(|region| other.with_ref_ok(|other| region.op_region(other, op)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_translate(mut region: BorrowedPtr<Region>, dx: i32, dy: i32) {
+    region
+        .with_mut_ok(|region| region.translate((dx, dy)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_contains_point(region: BorrowedPtr<Region>, x: i32, y: i32) -> bool {
+    region
+        .with_ref_ok(|region| region.contains_point(IPoint::new(x, y)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_contains_rect(
+    region: BorrowedPtr<Region>,
+    rect: BorrowedPtr<IRect>,
+) -> bool {
+    region
+        .with_ref(|region| rect.with_ref_ok(|rect| region.contains_rect(rect)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_contains_region(
+    region: BorrowedPtr<Region>,
+    other: BorrowedPtr<Region>,
+) -> bool {
+    region
+        .with_ref(|region| other.with_ref_ok(|other| region.contains_region(other)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_intersects_rect(
+    region: BorrowedPtr<Region>,
+    rect: BorrowedPtr<IRect>,
+) -> bool {
+    region
+        .with_ref(|region| rect.with_ref_ok(|rect| region.intersects_rect(rect)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_intersects_region(
+    region: BorrowedPtr<Region>,
+    other: BorrowedPtr<Region>,
+) -> bool {
+    region
+        .with_ref(|region| other.with_ref_ok(|other| region.intersects_region(other)))
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_get_bounds(region: BorrowedPtr<Region>) -> OwnedPtr<IRect> {
+    region
+        .with_ref_ok(|region| OwnedPtr::new(*region.bounds()))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_is_empty(region: BorrowedPtr<Region>) -> bool {
+    region.with_ref_ok(|region| region.is_empty()).or_log(true)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_is_rect(region: BorrowedPtr<Region>) -> bool {
+    region.with_ref_ok(|region| region.is_rect()).or_log(false)
+}
+
+/// Returns true if the region consists of more than one rectangle
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_is_complex(region: BorrowedPtr<Region>) -> bool {
+    region
+        .with_ref_ok(|region| region.is_complex())
+        .or_log(false)
+}
+
+/// Writes the non-overlapping rectangles that make up the region into `rects`,
+/// sorted top to bottom and left to right
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_get_rects(
+    region: BorrowedPtr<Region>,
+    mut rects: BorrowedPtr<ArrayBox<IRect>>,
+) {
+    region
+        .with_ref(|region| {
+            rects.with_mut_ok(|rects| {
+                let region_rects: Vec<IRect> = RegionIterator::new(region).collect();
+                rects.set_array(region_rects.as_slice());
+            })
+        })
+        .log();
+}
+
+/// Returns the outline of the region as a path
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_get_boundary_path(region: BorrowedPtr<Region>) -> OwnedPtr<Path> {
+    region
+        .with_ref_ok(|region| {
+            let mut path = Path::new();
+            region.get_boundary_path(&mut path);
+            OwnedPtr::new(path)
+        })
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_region_drop(region: OwnedPtr<Region>) {
+    drop(region);
+}
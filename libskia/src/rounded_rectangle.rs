@@ -1,5 +1,5 @@
-use skia_safe::rrect::Type;
-use skia_safe::{RRect, Rect, Vector, scalar};
+use skia_safe::rrect::{Corner, Type};
+use skia_safe::{Contains, Matrix, Path, Point, RRect, Rect, Vector, scalar};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
 #[unsafe(no_mangle)]
@@ -33,6 +33,29 @@ pub extern "C" fn skia_rounded_rectangle_new_radii(
     OwnedPtr::new(RRect::new_rect_radii(rect, &radii))
 }
 
+/// Creates a rounded rectangle whose corners share their horizontal radii on the left and right
+/// and their vertical radii on the top and bottom
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_new_nine_patch(
+    rectangle: BorrowedPtr<Rect>,
+    left_radius: scalar,
+    top_radius: scalar,
+    right_radius: scalar,
+    bottom_radius: scalar,
+) -> OwnedPtr<RRect> {
+    rectangle
+        .with_ref_ok(|rectangle| {
+            OwnedPtr::new(RRect::new_nine_patch(
+                rectangle,
+                left_radius,
+                top_radius,
+                right_radius,
+                bottom_radius,
+            ))
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_rounded_rectangle_get_type(rounded_rectangle: BorrowedPtr<RRect>) -> Type {
     rounded_rectangle
@@ -82,6 +105,119 @@ pub extern "C" fn skia_rounded_rectangle_set_oval(
         .log();
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_get_rect(
+    rounded_rectangle: BorrowedPtr<RRect>,
+) -> OwnedPtr<Rect> {
+    rounded_rectangle
+        .with_ref_ok(|rounded_rectangle| OwnedPtr::new(*rounded_rectangle.rect()))
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_get_radii(
+    rounded_rectangle: BorrowedPtr<RRect>,
+    corner: Corner,
+) -> OwnedPtr<Vector> {
+    rounded_rectangle
+        .with_ref_ok(|rounded_rectangle| OwnedPtr::new(rounded_rectangle.radii(corner)))
+        .or_log(OwnedPtr::null())
+}
+
+/// Sets the radii of a single corner. The radii of all corners are scaled down
+/// if they do not fit into the rectangle
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_set_radii(
+    mut rounded_rectangle: BorrowedPtr<RRect>,
+    corner: Corner,
+    radius_x: scalar,
+    radius_y: scalar,
+) {
+    rounded_rectangle
+        .with_mut_ok(|rounded_rectangle| {
+            let rectangle = *rounded_rectangle.rect();
+            let mut radii = *rounded_rectangle.radii_ref();
+            radii[corner as usize] = Vector::new(radius_x, radius_y);
+            rounded_rectangle.set_rect_radii(rectangle, &radii);
+        })
+        .log();
+}
+
+/// Moves the edges towards the center and shrinks the radii by the same amount,
+/// negative values move the edges outwards
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_inset(
+    mut rounded_rectangle: BorrowedPtr<RRect>,
+    dx: scalar,
+    dy: scalar,
+) {
+    rounded_rectangle
+        .with_mut_ok(|rounded_rectangle| rounded_rectangle.inset((dx, dy)))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_offset(
+    mut rounded_rectangle: BorrowedPtr<RRect>,
+    dx: scalar,
+    dy: scalar,
+) {
+    rounded_rectangle
+        .with_mut_ok(|rounded_rectangle| rounded_rectangle.offset((dx, dy)))
+        .log();
+}
+
+/// Returns true if the point is inside the rounded rectangle, points outside of the rounded corners are not contained
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_contains_point(
+    rounded_rectangle: BorrowedPtr<RRect>,
+    x: scalar,
+    y: scalar,
+) -> bool {
+    rounded_rectangle
+        .with_ref_ok(|rounded_rectangle| {
+            Path::rrect(rounded_rectangle, None).contains(Point::new(x, y))
+        })
+        .or_log(false)
+}
+
+/// Returns true if the rectangle is inside the rounded rectangle, including its rounded corners
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_contains_rectangle(
+    rounded_rectangle: BorrowedPtr<RRect>,
+    rectangle: BorrowedPtr<Rect>,
+) -> bool {
+    rounded_rectangle
+        .with_ref(|rounded_rectangle| {
+            rectangle.with_ref_ok(|rectangle| rounded_rectangle.contains(rectangle))
+        })
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_is_valid(rounded_rectangle: BorrowedPtr<RRect>) -> bool {
+    rounded_rectangle
+        .with_ref_ok(|rounded_rectangle| rounded_rectangle.is_valid())
+        .or_log(false)
+}
+
+/// Returns the transformed rounded rectangle or null if the matrix does not
+/// preserve axis alignment, for example it rotates or skews
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_rounded_rectangle_transform(
+    rounded_rectangle: BorrowedPtr<RRect>,
+    matrix: BorrowedPtr<Matrix>,
+) -> OwnedPtr<RRect> {
+    rounded_rectangle
+        .with_ref(|rounded_rectangle| {
+            matrix.with_ref_ok(|matrix| match rounded_rectangle.transform(matrix) {
+                None => OwnedPtr::null(),
+                Some(transformed) => OwnedPtr::new(transformed),
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_rounded_rectangle_drop(rounded_rectangle: OwnedPtr<RRect>) {
     drop(rounded_rectangle);
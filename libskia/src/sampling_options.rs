@@ -0,0 +1,70 @@
+use skia_safe::{CubicResampler, FilterMode, MipmapMode, SamplingOptions, scalar};
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_default() -> OwnedPtr<SamplingOptions> {
+    OwnedPtr::new(SamplingOptions::default())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_new(
+    filter_mode: FilterMode,
+    mipmap_mode: MipmapMode,
+) -> OwnedPtr<SamplingOptions> {
+    OwnedPtr::new(SamplingOptions::new(filter_mode, mipmap_mode))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_new_cubic(
+    b: scalar,
+    c: scalar,
+) -> OwnedPtr<SamplingOptions> {
+    OwnedPtr::new(SamplingOptions::from(CubicResampler { b, c }))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_new_cubic_mitchell() -> OwnedPtr<SamplingOptions> {
+    OwnedPtr::new(SamplingOptions::from(CubicResampler::mitchell()))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_new_cubic_catmull_rom() -> OwnedPtr<SamplingOptions> {
+    OwnedPtr::new(SamplingOptions::from(CubicResampler::catmull_rom()))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_new_aniso(max_aniso: i32) -> OwnedPtr<SamplingOptions> {
+    OwnedPtr::new(SamplingOptions::from_aniso(max_aniso))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_is_cubic(
+    sampling_options: BorrowedPtr<SamplingOptions>,
+) -> bool {
+    sampling_options
+        .with_ref_ok(|sampling_options| sampling_options.use_cubic)
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_get_filter_mode(
+    sampling_options: BorrowedPtr<SamplingOptions>,
+) -> FilterMode {
+    sampling_options
+        .with_ref_ok(|sampling_options| sampling_options.filter)
+        .or_log(FilterMode::Nearest)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_get_mipmap_mode(
+    sampling_options: BorrowedPtr<SamplingOptions>,
+) -> MipmapMode {
+    sampling_options
+        .with_ref_ok(|sampling_options| sampling_options.mipmap)
+        .or_log(MipmapMode::None)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_sampling_options_drop(sampling_options: OwnedPtr<SamplingOptions>) {
+    drop(sampling_options);
+}
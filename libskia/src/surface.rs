@@ -1,8 +1,13 @@
 use array_box::ArrayBox;
 use log::warn;
-use skia_safe::{AlphaType, Canvas, ColorType, IPoint, ISize, Image, ImageInfo, Surface, surfaces};
+use skia_safe::{
+    AlphaType, Canvas, ColorType, IPoint, IRect, ISize, Image, ImageInfo, Pixmap, Surface,
+    SurfaceProps, surfaces,
+};
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
+use crate::pixmap::detach_pixmap;
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_surface_new_raster_direct(
     image_info: BorrowedPtr<ImageInfo>,
@@ -39,6 +44,33 @@ pub extern "C" fn skia_surface_new_raster_n32_premul(width: i32, height: i32) ->
     }
 }
 
+/// Creates a raster surface that owns its pixels. Color type, alpha type and color space are
+/// taken from `image_info`. Pass 0 as `row_bytes` to use the minimal row bytes.
+/// `surface_props` may be null, in which case default surface properties are used
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_surface_new_raster(
+    image_info: BorrowedPtr<ImageInfo>,
+    row_bytes: usize,
+    surface_props: BorrowedPtr<SurfaceProps>,
+) -> OwnedPtr<Surface> {
+    image_info
+        .with_ref(|image_info| {
+            surface_props.with_option_ref(|surface_props| {
+                let surface_option = surfaces::raster(image_info, row_bytes, surface_props);
+                Ok(match surface_option {
+                    None => {
+                        if cfg!(debug_assertions) {
+                            eprintln!("[skia_surface_new_raster] could not create a surface width: {:?} height: {:?} color type {:?} alpha type {:?}", image_info.width(), image_info.height(), image_info.color_type(), image_info.alpha_type());
+                        }
+                        OwnedPtr::null()
+                    }
+                    Some(surface) => OwnedPtr::new(surface),
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_surface_new_default() -> OwnedPtr<Surface> {
     let surface_option = surfaces::raster_n32_premul(ISize::new(600, 400));
@@ -108,6 +140,15 @@ pub extern "C" fn skia_surface_get_height(surface: BorrowedPtr<Surface>) -> i32
     surface.with_ref_ok(|surface| surface.height()).or_log(0)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_surface_get_surface_props(
+    surface: BorrowedPtr<Surface>,
+) -> OwnedPtr<SurfaceProps> {
+    surface
+        .with_ref_ok(|surface| OwnedPtr::new(*surface.props()))
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_surface_get_image_info(
     mut surface: BorrowedPtr<Surface>,
@@ -138,6 +179,86 @@ pub extern "C" fn skia_surface_read_all_pixels(
         .or_log(false)
 }
 
+/// Returns a pixmap with direct access to the pixels of a raster surface or null
+/// if the surface is GPU backed.
+///
+/// # Safety
+///
+/// The returned [`Pixmap`] points to the pixels of `surface` and must not outlive it.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_surface_peek_pixels(
+    mut surface: BorrowedPtr<Surface>,
+) -> OwnedPtr<Pixmap<'static>> {
+    surface
+        .with_mut_ok(|surface| match surface.peek_pixels() {
+            None => OwnedPtr::null(),
+            Some(pixmap) => OwnedPtr::new(unsafe { detach_pixmap(pixmap) }),
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Reads a rectangle of pixels starting at `src_x` and `src_y` into `pixels`.
+/// The size of the rectangle as well as the color type, alpha type and color space
+/// of the destination are taken from `image_info`, pixels are converted if needed.
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_surface_read_pixels(
+    mut surface: BorrowedPtr<Surface>,
+    src_x: i32,
+    src_y: i32,
+    image_info: BorrowedPtr<ImageInfo>,
+    mut pixels: BorrowedPtr<ArrayBox<u8>>,
+    row_bytes: usize,
+) -> bool {
+    surface
+        .with_mut(|surface| {
+            image_info.with_ref(|image_info| {
+                pixels.with_mut_ok(|pixels| {
+                    surface.read_pixels(
+                        image_info,
+                        pixels.to_slice_mut(),
+                        row_bytes,
+                        IPoint::new(src_x, src_y),
+                    )
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Writes `pixels` described by `image_info` into the surface at `dst_x` and `dst_y`,
+/// converting them to the color type and color space of the surface.
+/// Returns false if `pixels` do not fit the image info or if they are written outside of the surface
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_surface_write_pixels(
+    mut surface: BorrowedPtr<Surface>,
+    dst_x: i32,
+    dst_y: i32,
+    image_info: BorrowedPtr<ImageInfo>,
+    mut pixels: BorrowedPtr<ArrayBox<u8>>,
+    row_bytes: usize,
+) -> bool {
+    surface
+        .with_mut(|surface| {
+            image_info.with_ref(|image_info| {
+                pixels.with_mut_ok(|pixels| {
+                    let destination = IRect::from_pt_size((dst_x, dst_y), image_info.dimensions());
+                    let bounds = IRect::from_wh(surface.width(), surface.height());
+                    if !IRect::intersects(&destination, &bounds) {
+                        return false;
+                    }
+                    match Pixmap::new(image_info, pixels.to_slice_mut(), row_bytes) {
+                        None => false,
+                        Some(pixmap) => {
+                            surface.write_pixels_from_pixmap(&pixmap, IPoint::new(dst_x, dst_y));
+                            true
+                        }
+                    }
+                })
+            })
+        })
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_surface_get_image_snapshot(
     mut surface: BorrowedPtr<Surface>,
@@ -1,22 +1,105 @@
+use std::collections::HashMap;
 use std::error::Error;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 
+use array_box::ArrayBox;
 use skia_safe::svg::Canvas as SvgCanvas;
-use skia_safe::svg::Dom;
 use skia_safe::svg::canvas::Flags as SvgCanvasFlags;
-use skia_safe::{Canvas, FontMgr, Rect, Size, Vector, scalar};
+use skia_safe::svg::preserve_aspect_ratio::{Align, Scale};
+use skia_safe::svg::{
+    Display, Dom, Length, LengthUnit, Node, NodeTag, Paint as SvgPaint, PreserveAspectRatio, Svg,
+    TypedNode, Visibility,
+};
+use skia_safe::{Canvas, Color, FontMgr, Matrix, Rect, Size, Vector, scalar};
 use string_box::StringBox;
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
+use crate::svg_resources::SvgResourceProvider;
+
+/// A parsed SVG document together with the elements that have an id.
+/// The DOM does not keep the ids, so they are looked up in the source once when it is parsed.
+/// The source is scanned apart from the DOM, so every node found for an id is checked to have
+/// the tags of the elements on the way to it, an id that leads elsewhere is not found
+pub struct SvgDom {
+    dom: Dom,
+    /// The indices and the tags of the children leading from the root to the element with the id
+    ids: HashMap<String, Vec<(usize, NodeTag)>>,
+}
+
+impl SvgDom {
+    /// Creates the document from the DOM and the source it was parsed from
+    pub fn new(svg: &str, dom: Dom) -> Self {
+        let mut ids = HashMap::new();
+        if let Some(root) = SourceElement::parse(svg).filter(|root| root.tag == NodeTag::Svg) {
+            root.collect_ids(&dom.root().into_node(), &mut vec![], &mut ids);
+        }
+        Self { dom, ids }
+    }
+
+    /// Returns the node of the element with the id, the first one if several elements have it
+    pub fn find_node_by_id(&self, id: &str) -> Option<Node> {
+        self.resolve(id).map(|(_, node)| node)
+    }
+
+    /// Returns the indices of the children leading to the element with the id and its node,
+    /// none if a node on the way does not have the tag of the element in the source
+    fn resolve(&self, id: &str) -> Option<(Vec<usize>, Node)> {
+        let steps = self.ids.get(id)?;
+        let node = steps
+            .iter()
+            .try_fold(self.dom.root().into_node(), |node, (index, tag)| {
+                element_children(&node)
+                    .into_iter()
+                    .nth(*index)
+                    .filter(|child| child.tag() == *tag)
+            })?;
+        Some((steps.iter().map(|(index, _)| *index).collect(), node))
+    }
+}
+
+impl Deref for SvgDom {
+    type Target = Dom;
+
+    fn deref(&self) -> &Self::Target {
+        &self.dom
+    }
+}
+
+impl DerefMut for SvgDom {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.dom
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_svg_parse(
     svg_string: BorrowedPtr<StringBox>,
     font_mgr: BorrowedPtr<FontMgr>,
-) -> OwnedPtr<Dom> {
+) -> OwnedPtr<SvgDom> {
     svg_string
         .with_ref(|svg_string| {
             font_mgr.with_clone(|font_mgr| {
                 Dom::from_str(svg_string.as_str(), font_mgr)
+                    .map(|dom| SvgDom::new(svg_string.as_str(), dom))
+                    .map_err(|error| (Box::new(error) as Box<dyn Error>).into())
+            })
+        })
+        .map(|dom| OwnedPtr::new(dom))
+        .or_log(OwnedPtr::null())
+}
+
+/// Parses the document resolving its images and fonts with the resource provider,
+/// which is copied, so later changes to it do not affect the document
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_parse_with_resources(
+    svg_string: BorrowedPtr<StringBox>,
+    resource_provider: BorrowedPtr<SvgResourceProvider>,
+) -> OwnedPtr<SvgDom> {
+    svg_string
+        .with_ref(|svg_string| {
+            resource_provider.with_clone(|resource_provider| {
+                Dom::from_str(svg_string.as_str(), resource_provider)
+                    .map(|dom| SvgDom::new(svg_string.as_str(), dom))
                     .map_err(|error| (Box::new(error) as Box<dyn Error>).into())
             })
         })
@@ -26,7 +109,7 @@ pub extern "C" fn skia_svg_parse(
 
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_svg_set_container_size(
-    mut dom: BorrowedPtr<Dom>,
+    mut dom: BorrowedPtr<SvgDom>,
     width: scalar,
     height: scalar,
 ) {
@@ -37,7 +120,7 @@ pub extern "C" fn skia_svg_set_container_size(
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_canvas_render_svg(
     canvas: BorrowedPtr<Canvas>,
-    dom: BorrowedPtr<Dom>,
+    dom: BorrowedPtr<SvgDom>,
     x: scalar,
     y: scalar,
 ) {
@@ -53,8 +136,634 @@ pub extern "C" fn skia_canvas_render_svg(
         .log();
 }
 
+/// Elements the SVG DOM creates nodes for and the tags of their nodes,
+/// the others are skipped together with their children. Links are created as groups
+const ELEMENT_TAGS: [(&str, NodeTag); 45] = [
+    ("a", NodeTag::G),
+    ("circle", NodeTag::Circle),
+    ("clipPath", NodeTag::ClipPath),
+    ("defs", NodeTag::Defs),
+    ("ellipse", NodeTag::Ellipse),
+    ("feBlend", NodeTag::FeBlend),
+    ("feColorMatrix", NodeTag::FeColorMatrix),
+    ("feComponentTransfer", NodeTag::FeComponentTransfer),
+    ("feComposite", NodeTag::FeComposite),
+    ("feDiffuseLighting", NodeTag::FeDiffuseLighting),
+    ("feDisplacementMap", NodeTag::FeDisplacementMap),
+    ("feDistantLight", NodeTag::FeDistantLight),
+    ("feFlood", NodeTag::FeFlood),
+    ("feFuncA", NodeTag::FeFuncA),
+    ("feFuncB", NodeTag::FeFuncB),
+    ("feFuncG", NodeTag::FeFuncG),
+    ("feFuncR", NodeTag::FeFuncR),
+    ("feGaussianBlur", NodeTag::FeGaussianBlur),
+    ("feImage", NodeTag::FeImage),
+    ("feMerge", NodeTag::FeMerge),
+    ("feMergeNode", NodeTag::FeMergeNode),
+    ("feMorphology", NodeTag::FeMorphology),
+    ("feOffset", NodeTag::FeOffset),
+    ("fePointLight", NodeTag::FePointLight),
+    ("feSpecularLighting", NodeTag::FeSpecularLighting),
+    ("feSpotLight", NodeTag::FeSpotLight),
+    ("feTurbulence", NodeTag::FeTurbulence),
+    ("filter", NodeTag::Filter),
+    ("g", NodeTag::G),
+    ("image", NodeTag::Image),
+    ("line", NodeTag::Line),
+    ("linearGradient", NodeTag::LinearGradient),
+    ("mask", NodeTag::Mask),
+    ("path", NodeTag::Path),
+    ("pattern", NodeTag::Pattern),
+    ("polygon", NodeTag::Polygon),
+    ("polyline", NodeTag::Polyline),
+    ("radialGradient", NodeTag::RadialGradient),
+    ("rect", NodeTag::Rect),
+    ("stop", NodeTag::Stop),
+    ("svg", NodeTag::Svg),
+    ("text", NodeTag::Text),
+    ("textPath", NodeTag::TextPath),
+    ("tspan", NodeTag::TSpan),
+    ("use", NodeTag::Use),
+];
+
+fn element_tag(tag: &str) -> Option<NodeTag> {
+    ELEMENT_TAGS
+        .iter()
+        .find(|(name, _)| *name == tag)
+        .map(|(_, node_tag)| *node_tag)
+}
+
+/// An element of the SVG source that has a node in the DOM,
+/// the nodes of the elements are found by walking both trees side by side
+struct SourceElement {
+    tag: NodeTag,
+    id: Option<String>,
+    children: Vec<SourceElement>,
+}
+
+impl SourceElement {
+    /// Scans the elements of the SVG source, none if it has no root element or declares entities,
+    /// which may expand to elements
+    fn parse(svg: &str) -> Option<Self> {
+        // the open elements, none for the skipped ones
+        let mut open: Vec<Option<SourceElement>> = vec![];
+        let mut root = None;
+        let mut rest = svg;
+
+        while let Some(start) = rest.find('<') {
+            rest = &rest[start..];
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = skip_past(comment, "-->");
+            } else if let Some(data) = rest.strip_prefix("<![CDATA[") {
+                rest = skip_past(data, "]]>");
+            } else if let Some(instruction) = rest.strip_prefix("<?") {
+                rest = skip_past(instruction, "?>");
+            } else if let Some(declaration) = rest.strip_prefix("<!") {
+                rest = skip_declaration(declaration);
+                if declaration[..declaration.len() - rest.len()].contains("<!ENTITY") {
+                    return None;
+                }
+            } else if let Some(end_tag) = rest.strip_prefix("</") {
+                rest = skip_past(end_tag, ">");
+                if let Some(element) = open.pop() {
+                    Self::close(element, &mut open, &mut root);
+                }
+            } else {
+                let (tag, id, is_empty, after) = read_start_tag(&rest[1..]);
+                rest = after;
+                let element = match (open.last(), element_tag(tag)) {
+                    (Some(None), _) | (_, None) => None,
+                    (Some(Some(parent)), Some(tag)) if !parent.accepts(tag) => None,
+                    (_, Some(tag)) => Some(SourceElement {
+                        tag,
+                        id,
+                        children: vec![],
+                    }),
+                };
+                if is_empty {
+                    Self::close(element, &mut open, &mut root);
+                } else {
+                    open.push(element);
+                }
+            }
+        }
+        root
+    }
+
+    fn close(
+        element: Option<SourceElement>,
+        open: &mut [Option<SourceElement>],
+        root: &mut Option<SourceElement>,
+    ) {
+        let Some(element) = element else {
+            return;
+        };
+        match open.last_mut() {
+            Some(Some(parent)) => parent.children.push(element),
+            Some(None) => {}
+            None => {
+                root.get_or_insert(element);
+            }
+        }
+    }
+
+    /// Text elements only keep the text spans and the text paths
+    fn accepts(&self, tag: NodeTag) -> bool {
+        match self.tag {
+            NodeTag::Text | NodeTag::TSpan | NodeTag::TextPath => {
+                matches!(tag, NodeTag::TSpan | NodeTag::TextPath)
+            }
+            _ => true,
+        }
+    }
+
+    /// Adds the paths to this element and its children that have an id, `path` leads to this element
+    fn collect_ids(
+        &self,
+        node: &Node,
+        path: &mut Vec<(usize, NodeTag)>,
+        ids: &mut HashMap<String, Vec<(usize, NodeTag)>>,
+    ) {
+        if let Some(id) = &self.id {
+            ids.entry(id.clone()).or_insert_with(|| path.clone());
+        }
+        for (index, (element, child)) in
+            self.children.iter().zip(element_children(node)).enumerate()
+        {
+            path.push((index, element.tag));
+            element.collect_ids(&child, path, ids);
+            path.pop();
+        }
+    }
+}
+
+fn skip_past<'a>(text: &'a str, end: &str) -> &'a str {
+    text.find(end)
+        .map(|index| &text[index + end.len()..])
+        .unwrap_or("")
+}
+
+/// Skips a declaration such as `<!DOCTYPE ...>` including its internal subset
+fn skip_declaration(text: &str) -> &str {
+    let mut depth = 0;
+    for (index, character) in text.char_indices() {
+        match character {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '>' if depth <= 0 => return &text[index + 1..],
+            _ => {}
+        }
+    }
+    ""
+}
+
+/// Reads a start tag after its `<`, returns the tag, the id, whether the element is empty
+/// and the text after the tag
+fn read_start_tag(text: &str) -> (&str, Option<String>, bool, &str) {
+    let is_delimiter = |character: char| {
+        character.is_whitespace() || character == '/' || character == '>' || character == '='
+    };
+    let tag_end = text.find(is_delimiter).unwrap_or(text.len());
+    let tag = &text[..tag_end];
+    let mut id = None;
+    let mut rest = &text[tag_end..];
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return (tag, id, true, rest);
+        }
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (tag, id, true, after);
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return (tag, id, false, after);
+        }
+
+        let name_end = rest.find(is_delimiter).unwrap_or(rest.len());
+        if name_end == 0 {
+            // a stray `/` or `=`
+            rest = &rest[1..];
+            continue;
+        }
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let Some(value) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let (value, after) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value[1..];
+                let end = value.find(quote).unwrap_or(value.len());
+                (&value[..end], value.get(end + 1..).unwrap_or(""))
+            }
+            _ => {
+                let end = value
+                    .find(|character: char| character.is_whitespace() || character == '>')
+                    .unwrap_or(value.len());
+                (&value[..end], &value[end..])
+            }
+        };
+        if name == "id" && id.is_none() {
+            id = Some(decode_references(value));
+        }
+        rest = after;
+    }
+}
+
+/// Replaces the character and predefined entity references of an attribute value,
+/// the unknown ones are kept as they are
+fn decode_references(value: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let character = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            reference => reference
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| reference.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match character {
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+            Some(character) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The children of a node that correspond to elements, without the text literals
+fn element_children(node: &Node) -> Vec<Node> {
+    let children = match node.clone().typed() {
+        TypedNode::ClipPath(node) => node.children().to_vec(),
+        TypedNode::Defs(node) => node.children().to_vec(),
+        TypedNode::Filter(node) => node.children().to_vec(),
+        TypedNode::G(node) => node.children().to_vec(),
+        TypedNode::LinearGradient(node) => node.children().to_vec(),
+        TypedNode::Mask(node) => node.children().to_vec(),
+        TypedNode::Pattern(node) => node.children().to_vec(),
+        TypedNode::RadialGradient(node) => node.children().to_vec(),
+        TypedNode::Svg(node) => node.children().to_vec(),
+        TypedNode::Text(node) => node.children().to_vec(),
+        TypedNode::TextPath(node) => node.children().to_vec(),
+        TypedNode::TSpan(node) => node.children().to_vec(),
+        _ => vec![],
+    };
+    children
+        .into_iter()
+        .filter(|child| !matches!(child.tag(), NodeTag::TextLiteral))
+        .collect()
+}
+
+/// Whether the node draws something when the document is rendered,
+/// as opposed to the resources such as gradients and clip paths
+fn is_rendered(node: &Node) -> bool {
+    matches!(
+        node.tag(),
+        NodeTag::Circle
+            | NodeTag::Ellipse
+            | NodeTag::G
+            | NodeTag::Image
+            | NodeTag::Line
+            | NodeTag::Path
+            | NodeTag::Polygon
+            | NodeTag::Polyline
+            | NodeTag::Rect
+            | NodeTag::Svg
+            | NodeTag::Text
+            | NodeTag::Use
+    )
+}
+
+/// Maps `source` into `destination` like the `preserveAspectRatio` attribute maps the view box
+fn aspect_ratio_matrix(
+    source: Rect,
+    destination: Rect,
+    preserve_aspect_ratio: PreserveAspectRatio,
+) -> Matrix {
+    let scale_x = destination.width() / source.width();
+    let scale_y = destination.height() / source.height();
+
+    let (scale_x, scale_y, align_x, align_y) = match preserve_aspect_ratio.align {
+        Align::None => (scale_x, scale_y, 0.0, 0.0),
+        align => {
+            let scale = match preserve_aspect_ratio.scale {
+                Scale::Meet => scale_x.min(scale_y),
+                Scale::Slice => scale_x.max(scale_y),
+            };
+            let (align_x, align_y) = match align {
+                Align::XMinYMin => (0.0, 0.0),
+                Align::XMidYMin => (0.5, 0.0),
+                Align::XMaxYMin => (1.0, 0.0),
+                Align::XMinYMid => (0.0, 0.5),
+                Align::XMidYMid => (0.5, 0.5),
+                Align::XMaxYMid => (1.0, 0.5),
+                Align::XMinYMax => (0.0, 1.0),
+                Align::XMidYMax => (0.5, 1.0),
+                Align::XMaxYMax | Align::None => (1.0, 1.0),
+            };
+            (scale, scale, align_x, align_y)
+        }
+    };
+
+    let mut matrix = Matrix::scale((scale_x, scale_y));
+    matrix.post_translate((
+        destination.left - source.left * scale_x
+            + (destination.width() - source.width() * scale_x) * align_x,
+        destination.top - source.top * scale_y
+            + (destination.height() - source.height() * scale_y) * align_y,
+    ));
+    matrix
+}
+
+/// Renders the document scaled into `destination` according to the `preserveAspectRatio`
+/// of the root `<svg>`, the content is clipped to `destination`.
+/// Without a view box the document is scaled by its intrinsic size, without both it is
+/// only moved to the origin of `destination`
+fn render_in_rect(dom: &mut Dom, canvas: &Canvas, destination: Rect) {
+    let root = dom.root();
+    let size = match root.view_box() {
+        Some(view_box) => view_box.size(),
+        None => root.intrinsic_size(),
+    };
+
+    canvas.save();
+    canvas.clip_rect(destination, None, None);
+    if size.is_empty() {
+        canvas.translate(Vector::new(destination.left, destination.top));
+        dom.render(canvas);
+    } else {
+        canvas.concat(&aspect_ratio_matrix(
+            Rect::from_size(size),
+            destination,
+            *root.preserve_aspect_ratio(),
+        ));
+        // the root then maps its view box with a scale of 1
+        let _root_size = RootSize::set(root, size);
+        dom.render(canvas);
+    }
+    canvas.restore();
+}
+
+/// Overrides the size of the root `<svg>` element, the original size is restored when dropped
+struct RootSize {
+    root: Svg,
+    width: Length,
+    height: Length,
+}
+
+impl RootSize {
+    fn set(mut root: Svg, size: Size) -> Self {
+        let (width, height) = (*root.width(), *root.height());
+        root.set_width(Length::new(size.width, LengthUnit::PX));
+        root.set_height(Length::new(size.height, LengthUnit::PX));
+        Self {
+            root,
+            width,
+            height,
+        }
+    }
+}
+
+impl Drop for RootSize {
+    fn drop(&mut self) {
+        self.root.set_width(self.width);
+        self.root.set_height(self.height);
+    }
+}
+
+/// Hides everything outside of the element at `path` while `render` runs,
+/// the resources such as gradients stay available
+fn render_only(dom: &mut Dom, path: &[usize], render: impl FnOnce(&mut Dom)) {
+    let mut hidden: Vec<(Node, Option<Display>)> = vec![];
+    let mut node = dom.root().into_node();
+    for &index in path {
+        let children = element_children(&node);
+        for (child_index, child) in children.iter().enumerate() {
+            if child_index != index && is_rendered(child) {
+                let mut child = child.clone();
+                hidden.push((child.clone(), child.display().copied()));
+                child.set_display(Display::None);
+            }
+        }
+        match children.into_iter().nth(index) {
+            None => break,
+            Some(child) => node = child,
+        }
+    }
+
+    render(dom);
+
+    // display is not inherited, so inline is the same as not set
+    for (mut node, display) in hidden {
+        node.set_display(display.unwrap_or(Display::Inline));
+    }
+}
+
+/// Returns the intrinsic width of the root `<svg>` element, 0 if it is relative or not set
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_get_intrinsic_width(dom: BorrowedPtr<SvgDom>) -> scalar {
+    dom.with_ref_ok(|dom| dom.root().intrinsic_size().width)
+        .or_log(0.0)
+}
+
+/// Returns the intrinsic height of the root `<svg>` element, 0 if it is relative or not set
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_get_intrinsic_height(dom: BorrowedPtr<SvgDom>) -> scalar {
+    dom.with_ref_ok(|dom| dom.root().intrinsic_size().height)
+        .or_log(0.0)
+}
+
+/// Returns the view box of the root `<svg>` element or null if it has none
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_get_view_box(dom: BorrowedPtr<SvgDom>) -> OwnedPtr<Rect> {
+    dom.with_ref_ok(|dom| match dom.root().view_box() {
+        None => OwnedPtr::null(),
+        Some(view_box) => OwnedPtr::new(*view_box),
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Renders the document scaled into `rect` according to its `preserveAspectRatio`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_render_svg_in_rect(
+    canvas: BorrowedPtr<Canvas>,
+    mut dom: BorrowedPtr<SvgDom>,
+    rect: BorrowedPtr<Rect>,
+) {
+    canvas
+        .with_ref(|canvas| {
+            dom.with_mut(|dom| rect.with_ref_ok(|rect| render_in_rect(dom, canvas, *rect)))
+        })
+        .log();
+}
+
+/// Renders only the element with the id and its children scaled into `rect` the same way as
+/// [`skia_canvas_render_svg_in_rect`], so that the element stays where it is in the document.
+/// Renders nothing if there is no element with the id
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_canvas_render_svg_node_in_rect(
+    canvas: BorrowedPtr<Canvas>,
+    mut dom: BorrowedPtr<SvgDom>,
+    id: BorrowedPtr<StringBox>,
+    rect: BorrowedPtr<Rect>,
+) {
+    canvas
+        .with_ref(|canvas| {
+            dom.with_mut(|dom| {
+                id.with_ref(|id| {
+                    rect.with_ref_ok(|rect| {
+                        if let Some((path, _)) = dom.resolve(id.as_str()) {
+                            render_only(dom, &path, |dom| render_in_rect(dom, canvas, *rect));
+                        }
+                    })
+                })
+            })
+        })
+        .log();
+}
+
+/// Finds the node of the element with the id, returns null if there is no such element.
+/// The node is shared with the document, changing its attributes changes how it renders
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_find_node_by_id(
+    dom: BorrowedPtr<SvgDom>,
+    id: BorrowedPtr<StringBox>,
+) -> OwnedPtr<Node> {
+    dom.with_ref(|dom| {
+        id.with_ref_ok(|id| match dom.find_node_by_id(id.as_str()) {
+            None => OwnedPtr::null(),
+            Some(node) => OwnedPtr::new(node),
+        })
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Returns the fill color set on the node, null if it is not set or is not a color
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_get_fill(node: BorrowedPtr<Node>) -> OwnedPtr<Color> {
+    node.with_ref_ok(|node| match node.fill().and_then(|fill| fill.color()) {
+        None => OwnedPtr::null(),
+        Some(color) => OwnedPtr::new(color),
+    })
+    .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_fill_rgb(mut node: BorrowedPtr<Node>, r: u8, g: u8, b: u8) {
+    node.with_mut_ok(|node| node.set_fill(SvgPaint::from_color(Color::from_rgb(r, g, b))))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_fill_none(mut node: BorrowedPtr<Node>) {
+    node.with_mut_ok(|node| node.set_fill(SvgPaint::none()))
+        .log();
+}
+
+/// Makes the node fill with its `color` property, which is inherited
 #[unsafe(no_mangle)]
-pub extern "C" fn skia_svg_dom_drop(dom: OwnedPtr<Dom>) {
+pub extern "C" fn skia_svg_node_set_fill_current_color(mut node: BorrowedPtr<Node>) {
+    node.with_mut_ok(|node| node.set_fill(SvgPaint::current_color()))
+        .log();
+}
+
+/// Returns the stroke color set on the node, null if it is not set or is not a color
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_get_stroke(node: BorrowedPtr<Node>) -> OwnedPtr<Color> {
+    node.with_ref_ok(
+        |node| match node.stroke().and_then(|stroke| stroke.color()) {
+            None => OwnedPtr::null(),
+            Some(color) => OwnedPtr::new(color),
+        },
+    )
+    .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_stroke_rgb(mut node: BorrowedPtr<Node>, r: u8, g: u8, b: u8) {
+    node.with_mut_ok(|node| node.set_stroke(SvgPaint::from_color(Color::from_rgb(r, g, b))))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_stroke_none(mut node: BorrowedPtr<Node>) {
+    node.with_mut_ok(|node| node.set_stroke(SvgPaint::none()))
+        .log();
+}
+
+/// Returns the `color` property set on the node, null if it is not set
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_get_color(node: BorrowedPtr<Node>) -> OwnedPtr<Color> {
+    node.with_ref_ok(|node| match node.color() {
+        None => OwnedPtr::null(),
+        Some(color) => OwnedPtr::new(*color),
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Sets the `color` property used by the fills and strokes with `currentColor`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_color_rgb(mut node: BorrowedPtr<Node>, r: u8, g: u8, b: u8) {
+    node.with_mut_ok(|node| node.set_color(Color::from_rgb(r, g, b)))
+        .log();
+}
+
+/// Returns the visibility set on the node, `Inherit` if it is not set
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_get_visibility(node: BorrowedPtr<Node>) -> Visibility {
+    node.with_ref_ok(|node| node.visibility().copied().unwrap_or(Visibility::Inherit))
+        .or_log(Visibility::Inherit)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_visibility(
+    mut node: BorrowedPtr<Node>,
+    visibility: Visibility,
+) {
+    node.with_mut_ok(|node| node.set_visibility(visibility))
+        .log();
+}
+
+/// Returns the opacity set on the node, 1 if it is not set
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_get_opacity(node: BorrowedPtr<Node>) -> scalar {
+    node.with_ref_ok(|node| node.opacity().unwrap_or(1.0))
+        .or_log(1.0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_set_opacity(mut node: BorrowedPtr<Node>, opacity: scalar) {
+    node.with_mut_ok(|node| node.set_opacity(opacity)).log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_node_drop(node: OwnedPtr<Node>) {
+    drop(node);
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_dom_drop(dom: OwnedPtr<SvgDom>) {
     drop(dom);
 }
 
@@ -71,6 +780,31 @@ pub extern "C" fn skia_svg_canvas_new(
     OwnedPtr::new(canvas)
 }
 
+/// Creates an SVG canvas with named options instead of raw flag bits.
+/// `convert_text_to_paths` writes text as paths, so that it renders without the fonts,
+/// `no_pretty_xml` omits the indentation and line breaks and `relative_path_encoding`
+/// writes path commands with relative coordinates
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_canvas_new_with_options(
+    left: scalar,
+    top: scalar,
+    width: scalar,
+    height: scalar,
+    convert_text_to_paths: bool,
+    no_pretty_xml: bool,
+    relative_path_encoding: bool,
+) -> OwnedPtr<SvgCanvas> {
+    let mut svg_flags = SvgCanvasFlags::empty();
+    svg_flags.set(SvgCanvasFlags::CONVERT_TEXT_TO_PATHS, convert_text_to_paths);
+    svg_flags.set(SvgCanvasFlags::NO_PRETTY_XML, no_pretty_xml);
+    svg_flags.set(
+        SvgCanvasFlags::RELATIVE_PATH_ENCODING,
+        relative_path_encoding,
+    );
+    let canvas = SvgCanvas::new(Rect::from_xywh(left, top, width, height), Some(svg_flags));
+    OwnedPtr::new(canvas)
+}
+
 /// # Safety
 ///
 /// The returned [`BorrowedPtr<Canvas>`] is borrowed from `svg_canvas` and must
@@ -84,22 +818,74 @@ pub extern "C" fn skia_svg_canvas_get_canvas(
         .or_log(BorrowedPtr::null())
 }
 
+/// Finishes the document and writes it into `data`.
+/// Returns false if the document is not valid UTF-8
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_svg_canvas_end(
     svg_canvas: OwnedPtr<SvgCanvas>,
     mut data: BorrowedPtr<StringBox>,
-) {
+) -> bool {
     svg_canvas
-        .with_value_ok(|svg_canvas| {
-            data.with_mut_ok(|data| {
-                let svg = svg_canvas.end();
-                let string = std::str::from_utf8(svg.as_bytes()).unwrap();
-                data.set_string(string.to_string());
+        .with_value_ok(|svg_canvas| svg_canvas.end())
+        .and_then(|svg| {
+            data.with_mut_ok(|data| match std::str::from_utf8(svg.as_bytes()) {
+                Ok(string) => {
+                    data.set_string(string.to_string());
+                    true
+                }
+                Err(error) => {
+                    if cfg!(debug_assertions) {
+                        eprintln!(
+                            "[skia_svg_canvas_end] The SVG is not valid UTF-8: {}",
+                            error
+                        );
+                    };
+                    false
+                }
             })
         })
+        .or_log(false)
+}
+
+/// Finishes the document and writes its bytes into `data`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_canvas_end_to_bytes(
+    svg_canvas: OwnedPtr<SvgCanvas>,
+    mut data: BorrowedPtr<ArrayBox<u8>>,
+) {
+    svg_canvas
+        .with_value_ok(|svg_canvas| svg_canvas.end())
+        .and_then(|svg| data.with_mut_ok(|data| data.set_array(svg.as_bytes())))
         .log();
 }
 
+/// Finishes the document and writes it to the file at `path`.
+/// Returns false if the file can not be written
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_canvas_end_to_file(
+    svg_canvas: OwnedPtr<SvgCanvas>,
+    path: BorrowedPtr<StringBox>,
+) -> bool {
+    svg_canvas
+        .with_value_ok(|svg_canvas| svg_canvas.end())
+        .and_then(|svg| {
+            path.with_ref_ok(|path| match std::fs::write(path.as_str(), svg.as_bytes()) {
+                Ok(()) => true,
+                Err(error) => {
+                    if cfg!(debug_assertions) {
+                        eprintln!(
+                            "[skia_svg_canvas_end_to_file] Could not write {}: {}",
+                            path.as_str(),
+                            error
+                        );
+                    };
+                    false
+                }
+            })
+        })
+        .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_svg_canvas_drop(svg_canvas: OwnedPtr<SvgCanvas>) {
     drop(svg_canvas);
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use array_box::ArrayBox;
+use skia_safe::resources::ResourceProvider;
+use skia_safe::resources::helpers::{ResourceKind, identify_resource_kind, load_typeface};
+use skia_safe::{Data, FontMgr, Typeface};
+use string_box::StringBox;
+use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
+
+/// Writes the bytes of the resource with the given name into the array and returns true,
+/// or returns false if the host does not provide the resource.
+/// The host may pass null, then the resources are loaded without asking it
+pub type SvgResourceCallback =
+    Option<extern "C" fn(BorrowedPtr<StringBox>, BorrowedPtr<ArrayBox<u8>>) -> bool>;
+
+/// Loads the images and fonts referenced by an SVG document.
+/// `data:` URIs are decoded, other resources are looked up by their relative path
+/// in the in-memory resources, then asked from the callback and finally read
+/// from the base directory. No file is read without a base directory, files outside of it
+/// only by an absolute path when they are allowed
+#[derive(Clone)]
+pub struct SvgResourceProvider {
+    font_mgr: FontMgr,
+    base_directory: Option<PathBuf>,
+    allow_absolute_paths: bool,
+    resources: HashMap<String, Data>,
+    callback: SvgResourceCallback,
+}
+
+impl SvgResourceProvider {
+    pub fn new(font_mgr: FontMgr) -> Self {
+        Self {
+            font_mgr,
+            base_directory: None,
+            allow_absolute_paths: false,
+            resources: HashMap::new(),
+            callback: None,
+        }
+    }
+
+    pub fn set_base_directory(&mut self, base_directory: impl Into<PathBuf>) {
+        self.base_directory = Some(base_directory.into());
+    }
+
+    /// Allows reading absolute paths and `file://` URIs anywhere, once there is a base directory
+    pub fn set_allow_absolute_paths(&mut self, allow_absolute_paths: bool) {
+        self.allow_absolute_paths = allow_absolute_paths;
+    }
+
+    pub fn add_resource(&mut self, name: impl Into<String>, data: Data) {
+        self.resources.insert(name.into(), data);
+    }
+
+    /// Sets the callback asked for the resources that are not added, none to stop asking
+    pub fn set_callback(&mut self, callback: SvgResourceCallback) {
+        self.callback = callback;
+    }
+
+    fn load_from_callback(&self, name: &str) -> Option<Data> {
+        let callback = self.callback?;
+        let name = StringBox::from_string(name.to_string());
+        let mut bytes = ArrayBox::<u8>::new();
+        if callback(
+            BorrowedPtr::from_ref(&name),
+            BorrowedPtr::from_mut(&mut bytes),
+        ) {
+            Some(Data::new_copy(bytes.to_slice()))
+        } else {
+            None
+        }
+    }
+
+    fn load_from_file(&self, name: &str) -> Option<Data> {
+        if name.contains("://") && !name.starts_with("file://") {
+            return None;
+        }
+        let path = Path::new(name.strip_prefix("file://").unwrap_or(name));
+        let path = match &self.base_directory {
+            // a document from an untrusted source must not read arbitrary local files
+            None => return None,
+            Some(_) if path.is_absolute() && self.allow_absolute_paths => path.to_path_buf(),
+            Some(_) if path.is_absolute() => return None,
+            Some(base_directory) => {
+                // `..` and symbolic links must not lead out of the base directory
+                let base_directory = base_directory.canonicalize().ok()?;
+                let path = base_directory.join(path).canonicalize().ok()?;
+                if !path.starts_with(&base_directory) {
+                    if cfg!(debug_assertions) {
+                        eprintln!(
+                            "[SvgResourceProvider] Refusing to read {:?} outside of the base directory",
+                            name
+                        );
+                    };
+                    return None;
+                }
+                path
+            }
+        };
+        std::fs::read(path).ok().map(|bytes| Data::new_copy(&bytes))
+    }
+}
+
+impl ResourceProvider for SvgResourceProvider {
+    fn load(&self, resource_path: &str, resource_name: &str) -> Option<Data> {
+        if resource_name.starts_with("data:") {
+            return decode_data_uri(resource_name);
+        }
+
+        // the SVG DOM splits the reference into its directory and its file name
+        let name = if resource_path.is_empty() {
+            resource_name.to_string()
+        } else {
+            format!("{resource_path}/{resource_name}")
+        };
+        self.resources
+            .get(&name)
+            .cloned()
+            .or_else(|| self.load_from_callback(&name))
+            .or_else(|| self.load_from_file(&name))
+    }
+
+    fn load_typeface(&self, name: &str, url: &str) -> Option<Typeface> {
+        load_typeface(self, &self.font_mgr, name, url)
+    }
+
+    fn font_mgr(&self) -> FontMgr {
+        self.font_mgr.clone()
+    }
+}
+
+/// Decodes a base64 or percent encoded `data:` URI, none if it is malformed
+fn decode_data_uri(uri: &str) -> Option<Data> {
+    let (header, content) = uri.split_once(',')?;
+    if header.ends_with(";base64") {
+        return match identify_resource_kind("", uri) {
+            ResourceKind::Base64(data) if !data.is_empty() => Some(data),
+            _ => None,
+        };
+    }
+    Some(Data::new_copy(&percent_decode(content)))
+}
+
+fn percent_decode(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    decoded
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_resource_provider_new(
+    font_mgr: BorrowedPtr<FontMgr>,
+) -> OwnedPtr<SvgResourceProvider> {
+    font_mgr
+        .with_clone_ok(|font_mgr| OwnedPtr::new(SvgResourceProvider::new(font_mgr)))
+        .or_log(OwnedPtr::null())
+}
+
+/// Sets the directory the relative paths and file names are resolved against,
+/// files outside of it are not read
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_resource_provider_set_base_directory(
+    mut resource_provider: BorrowedPtr<SvgResourceProvider>,
+    base_directory: BorrowedPtr<StringBox>,
+) {
+    resource_provider
+        .with_mut(|resource_provider| {
+            base_directory.with_ref_ok(|base_directory| {
+                resource_provider.set_base_directory(base_directory.as_str())
+            })
+        })
+        .log();
+}
+
+/// Allows reading absolute paths and `file://` URIs outside of the base directory.
+/// Without a base directory no file is read, whether they are allowed or not
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_resource_provider_set_allow_absolute_paths(
+    mut resource_provider: BorrowedPtr<SvgResourceProvider>,
+    allow_absolute_paths: bool,
+) {
+    resource_provider
+        .with_mut_ok(|resource_provider| {
+            resource_provider.set_allow_absolute_paths(allow_absolute_paths)
+        })
+        .log();
+}
+
+/// Adds a resource with the name it is referenced by in the document, such as `icons/logo.png`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_resource_provider_add_resource(
+    mut resource_provider: BorrowedPtr<SvgResourceProvider>,
+    name: BorrowedPtr<StringBox>,
+    bytes: BorrowedPtr<ArrayBox<u8>>,
+) {
+    resource_provider
+        .with_mut(|resource_provider| {
+            name.with_ref(|name| {
+                bytes.with_ref_ok(|bytes| {
+                    resource_provider
+                        .add_resource(name.to_string(), Data::new_copy(bytes.to_slice()))
+                })
+            })
+        })
+        .log();
+}
+
+/// Sets the callback asked for the resources that are not added to the provider.
+/// It is called while parsing and rendering, possibly long after the parse call returned.
+/// A null callback removes the previous one
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_resource_provider_set_callback(
+    mut resource_provider: BorrowedPtr<SvgResourceProvider>,
+    callback: SvgResourceCallback, // may be null
+) {
+    resource_provider
+        .with_mut_ok(|resource_provider| resource_provider.set_callback(callback))
+        .log();
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_svg_resource_provider_drop(
+    resource_provider: OwnedPtr<SvgResourceProvider>,
+) {
+    drop(resource_provider);
+}
@@ -1,7 +1,9 @@
 use array_box::ArrayBox;
 use skia_safe::font::Edging;
+use skia_safe::path::AddPathMode;
 use skia_safe::{
-    Font, FontHinting, FontMetrics, GlyphId, Paint, Rect, TextEncoding, Typeface, scalar,
+    Font, FontHinting, FontMetrics, GlyphId, Paint, Path, Point, Rect, TextEncoding, Typeface,
+    Unichar, scalar,
 };
 use string_box::StringBox;
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
@@ -21,18 +23,67 @@ pub extern "C" fn skia_font_from_typeface(
         .or_log(OwnedPtr::null())
 }
 
+/// Creates a font with the horizontal scale and skew used to synthesize condensed or italic styles
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_new(
+    typeface: BorrowedPtr<Typeface>,
+    size: scalar,
+    scale_x: scalar,
+    skew_x: scalar,
+) -> OwnedPtr<Font> {
+    typeface
+        .with_clone_ok(|typeface| {
+            OwnedPtr::new(Font::from_typeface_with_params(
+                typeface, size, scale_x, skew_x,
+            ))
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns a copy of the font with another size, null if the size is negative or not finite
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_with_size(font: BorrowedPtr<Font>, size: scalar) -> OwnedPtr<Font> {
+    font.with_ref_ok(|font| match font.with_size(size) {
+        None => OwnedPtr::null(),
+        Some(font) => OwnedPtr::new(font),
+    })
+    .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_is_force_auto_hinting(font: BorrowedPtr<Font>) -> bool {
     font.with_ref_ok(|font| font.is_force_auto_hinting())
         .or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_force_auto_hinting(
+    mut font: BorrowedPtr<Font>,
+    force_auto_hinting: bool,
+) {
+    font.with_mut_ok(|font| {
+        font.set_force_auto_hinting(force_auto_hinting);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_is_embedded_bitmaps(font: BorrowedPtr<Font>) -> bool {
     font.with_ref_ok(|font| font.is_embedded_bitmaps())
         .or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_embedded_bitmaps(
+    mut font: BorrowedPtr<Font>,
+    embedded_bitmaps: bool,
+) {
+    font.with_mut_ok(|font| {
+        font.set_embedded_bitmaps(embedded_bitmaps);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_is_subpixel(font: BorrowedPtr<Font>) -> bool {
     font.with_ref_ok(|font| font.is_subpixel()).or_log(false)
@@ -52,17 +103,41 @@ pub extern "C" fn skia_font_is_linear_metrics(font: BorrowedPtr<Font>) -> bool {
         .or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_linear_metrics(mut font: BorrowedPtr<Font>, linear_metrics: bool) {
+    font.with_mut_ok(|font| {
+        font.set_linear_metrics(linear_metrics);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_is_embolden(font: BorrowedPtr<Font>) -> bool {
     font.with_ref_ok(|font| font.is_embolden()).or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_embolden(mut font: BorrowedPtr<Font>, embolden: bool) {
+    font.with_mut_ok(|font| {
+        font.set_embolden(embolden);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_is_baseline_snap(font: BorrowedPtr<Font>) -> bool {
     font.with_ref_ok(|font| font.is_baseline_snap())
         .or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_baseline_snap(mut font: BorrowedPtr<Font>, baseline_snap: bool) {
+    font.with_mut_ok(|font| {
+        font.set_baseline_snap(baseline_snap);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_get_edging(font: BorrowedPtr<Font>) -> Edging {
     font.with_ref_ok(|font| font.edging()).or_log(Edging::Alias)
@@ -96,21 +171,58 @@ pub extern "C" fn skia_font_get_typeface_or_default(font: BorrowedPtr<Font>) ->
         .or_log(OwnedPtr::null())
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_typeface(
+    mut font: BorrowedPtr<Font>,
+    typeface: BorrowedPtr<Typeface>,
+) {
+    font.with_mut(|font| {
+        typeface.with_clone_ok(|typeface| {
+            font.set_typeface(typeface);
+        })
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_get_size(font: BorrowedPtr<Font>) -> scalar {
     font.with_ref_ok(|font| font.size()).or_log(0.0)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_size(mut font: BorrowedPtr<Font>, size: scalar) {
+    font.with_mut_ok(|font| {
+        font.set_size(size);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_get_scale_x(font: BorrowedPtr<Font>) -> scalar {
     font.with_ref_ok(|font| font.scale_x()).or_log(0.0)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_scale_x(mut font: BorrowedPtr<Font>, scale_x: scalar) {
+    font.with_mut_ok(|font| {
+        font.set_scale_x(scale_x);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_get_skew_x(font: BorrowedPtr<Font>) -> scalar {
     font.with_ref_ok(|font| font.skew_x()).or_log(0.0)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_set_skew_x(mut font: BorrowedPtr<Font>, skew_x: scalar) {
+    font.with_mut_ok(|font| {
+        font.set_skew_x(skew_x);
+    })
+    .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_get_spacing(font: BorrowedPtr<Font>) -> scalar {
     font.with_ref_ok(|font| font.spacing()).or_log(0.0)
@@ -122,6 +234,7 @@ pub extern "C" fn skia_font_get_metrics(font: BorrowedPtr<Font>) -> OwnedPtr<Fon
         .or_log(OwnedPtr::null())
 }
 
+/// The string is always UTF-8, use [`skia_font_encoded_text_to_glyphs`] for the other encodings
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_text_to_glyphs(
     font: BorrowedPtr<Font>,
@@ -163,6 +276,210 @@ pub extern "C" fn skia_font_text_to_glyphs(
     .or_log(0.0)
 }
 
+/// Decodes text in native byte order, none if it is not valid in the encoding
+fn decode_text(bytes: &[u8], encoding: TextEncoding) -> Option<String> {
+    match encoding {
+        TextEncoding::UTF8 => String::from_utf8(bytes.to_vec()).ok(),
+        TextEncoding::UTF16 => {
+            let units = native_units(bytes, u16::from_ne_bytes)?;
+            String::from_utf16(&units).ok()
+        }
+        TextEncoding::UTF32 => native_units(bytes, u32::from_ne_bytes)?
+            .into_iter()
+            .map(char::from_u32)
+            .collect(),
+        TextEncoding::GlyphId => None,
+    }
+}
+
+fn native_units<const N: usize, T>(bytes: &[u8], from_bytes: fn([u8; N]) -> T) -> Option<Vec<T>> {
+    if bytes.len() % N != 0 {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(N)
+            .map(|chunk| from_bytes(chunk.try_into().unwrap()))
+            .collect(),
+    )
+}
+
+/// Converts UTF-8, UTF-16, UTF-32 or glyph id encoded bytes to glyphs and returns their amount.
+/// Multi-byte units are in native byte order, malformed text results in no glyphs
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_encoded_text_to_glyphs(
+    font: BorrowedPtr<Font>,
+    text: BorrowedPtr<ArrayBox<u8>>,
+    encoding: TextEncoding,
+    mut glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+) -> usize {
+    font.with_ref(|font| {
+        text.with_ref(|text| {
+            glyphs.with_mut_ok(|glyphs| {
+                let bytes = text.to_slice();
+                let glyphs_vec = match encoding {
+                    TextEncoding::GlyphId => native_units(bytes, GlyphId::from_ne_bytes),
+                    _ => decode_text(bytes, encoding).map(|text| font.str_to_glyphs_vec(text)),
+                };
+                let glyphs_vec = glyphs_vec.unwrap_or_else(|| {
+                    if cfg!(debug_assertions) {
+                        eprintln!(
+                            "[skia_font_encoded_text_to_glyphs] Text is not valid {:?}",
+                            encoding
+                        )
+                    };
+                    vec![]
+                });
+                let count = glyphs_vec.len();
+                glyphs.set_vector(glyphs_vec);
+                count
+            })
+        })
+    })
+    .or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_unichar_to_glyph(font: BorrowedPtr<Font>, unichar: Unichar) -> GlyphId {
+    font.with_ref_ok(|font| font.unichar_to_glyph(unichar))
+        .or_log(0)
+}
+
+/// Writes the advance width of each glyph
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_get_widths(
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    mut widths: BorrowedPtr<ArrayBox<scalar>>,
+) {
+    font.with_ref(|font| {
+        glyphs.with_ref(|glyphs| {
+            widths.with_mut_ok(|widths| {
+                let glyphs = glyphs.to_slice();
+                let mut glyph_widths = vec![0.0; glyphs.len()];
+                font.get_widths(glyphs, &mut glyph_widths);
+                widths.set_vector(glyph_widths);
+            })
+        })
+    })
+    .log();
+}
+
+/// Writes the bounds of each glyph relative to its origin
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_get_bounds(
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    paint: BorrowedPtr<Paint>, // may be null
+    mut bounds: BorrowedPtr<ArrayBox<Rect>>,
+) {
+    font.with_ref(|font| {
+        glyphs.with_ref(|glyphs| {
+            paint.with_option_ref(|paint| {
+                bounds.with_mut_ok(|bounds| {
+                    let glyphs = glyphs.to_slice();
+                    let mut glyph_bounds = vec![Rect::default(); glyphs.len()];
+                    font.get_bounds(glyphs, &mut glyph_bounds, paint);
+                    bounds.set_vector(glyph_bounds);
+                })
+            })
+        })
+    })
+    .log();
+}
+
+/// Writes the position of each glyph when the run starts at the origin
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_get_pos(
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    origin_x: scalar,
+    origin_y: scalar,
+    mut positions: BorrowedPtr<ArrayBox<Point>>,
+) {
+    font.with_ref(|font| {
+        glyphs.with_ref(|glyphs| {
+            positions.with_mut_ok(|positions| {
+                let glyphs = glyphs.to_slice();
+                let mut glyph_positions = vec![Point::default(); glyphs.len()];
+                font.get_pos(
+                    glyphs,
+                    &mut glyph_positions,
+                    Some(Point::new(origin_x, origin_y)),
+                );
+                positions.set_vector(glyph_positions);
+            })
+        })
+    })
+    .log();
+}
+
+/// Writes the horizontal position of each glyph when the run starts at `origin_x`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_get_x_pos(
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    origin_x: scalar,
+    mut positions: BorrowedPtr<ArrayBox<scalar>>,
+) {
+    font.with_ref(|font| {
+        glyphs.with_ref(|glyphs| {
+            positions.with_mut_ok(|positions| {
+                let glyphs = glyphs.to_slice();
+                let mut glyph_positions = vec![0.0; glyphs.len()];
+                font.get_x_pos(glyphs, &mut glyph_positions, Some(origin_x));
+                positions.set_vector(glyph_positions);
+            })
+        })
+    })
+    .log();
+}
+
+/// Returns the outline of the glyph, null if it has none, such as a bitmap glyph
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_get_path(font: BorrowedPtr<Font>, glyph: GlyphId) -> OwnedPtr<Path> {
+    font.with_ref_ok(|font| match font.get_path(glyph) {
+        None => OwnedPtr::null(),
+        Some(path) => OwnedPtr::new(path),
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Appends the outline of each glyph moved to its position to the path.
+/// Glyphs without an outline are skipped, returns false if there is not a position per glyph
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_get_paths(
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    positions: BorrowedPtr<ArrayBox<Point>>,
+    mut path: BorrowedPtr<Path>,
+) -> bool {
+    font.with_ref(|font| {
+        glyphs.with_ref(|glyphs| {
+            positions.with_ref(|positions| {
+                path.with_mut_ok(|path| {
+                    if glyphs.length != positions.length {
+                        if cfg!(debug_assertions) {
+                            eprintln!(
+                                "[skia_font_get_paths] Got {} glyphs but {} positions",
+                                glyphs.length, positions.length
+                            )
+                        };
+                        return false;
+                    }
+                    for (glyph, position) in glyphs.to_slice().iter().zip(positions.to_slice()) {
+                        if let Some(glyph_path) = font.get_path(*glyph) {
+                            path.add_path(&glyph_path, *position, AddPathMode::Append);
+                        }
+                    }
+                    true
+                })
+            })
+        })
+    })
+    .or_log(false)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_measure_text(
     font: BorrowedPtr<Font>,
@@ -1,7 +1,8 @@
 use crate::text::font_style::FontStyleWidth;
+use crate::text::typeface::clone_with_variations;
 use array_box::ArrayBox;
 use skia_safe::font_style::{Slant, Weight};
-use skia_safe::{FontMgr, FontStyle, FontStyleSet, Typeface};
+use skia_safe::{FontMgr, FontStyle, FontStyleSet, Typeface, Unichar};
 use string_box::StringBox;
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
@@ -49,6 +50,53 @@ pub extern "C" fn skia_font_manager_new_typeface_from_data(
         .or_log(OwnedPtr::null())
 }
 
+/// Creates a typeface from the font at `ttc_index` in a font collection (0 for a single font)
+/// with the variation axes set to the values. Returns null if the data is not a font
+/// or the amounts of tags and values differ
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_manager_new_typeface_from_data_with_arguments(
+    font_manager: BorrowedPtr<FontMgr>,
+    data: BorrowedPtr<ArrayBox<u8>>,
+    ttc_index: usize,
+    axis_tags: BorrowedPtr<ArrayBox<u32>>,
+    values: BorrowedPtr<ArrayBox<f32>>,
+) -> OwnedPtr<Typeface> {
+    font_manager
+        .with_clone(|font_manager| {
+            data.with_ref(|data| {
+                axis_tags.with_ref(|axis_tags| {
+                    values.with_ref_ok(|values| {
+                        let (axis_tags, values) = (axis_tags.to_slice(), values.to_slice());
+                        if axis_tags.len() != values.len() {
+                            if cfg!(debug_assertions) {
+                                eprintln!(
+                                    "[skia_font_manager_new_typeface_from_data_with_arguments] Got {} axis tags but {} values",
+                                    axis_tags.len(),
+                                    values.len()
+                                );
+                            };
+                            return OwnedPtr::null();
+                        }
+                        let typeface = font_manager
+                            .new_from_data(data.to_slice(), ttc_index)
+                            .and_then(|typeface| {
+                                if axis_tags.is_empty() {
+                                    Some(typeface)
+                                } else {
+                                    clone_with_variations(&typeface, axis_tags, values)
+                                }
+                            });
+                        match typeface {
+                            None => OwnedPtr::null(),
+                            Some(typeface) => OwnedPtr::new(typeface),
+                        }
+                    })
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_manager_match_family(
     font_manager: BorrowedPtr<FontMgr>,
@@ -84,6 +132,73 @@ pub extern "C" fn skia_font_manager_match_family_style(
         .or_log(OwnedPtr::null())
 }
 
+/// Finds a typeface that has a glyph for the character, preferring the family and style.
+/// `bcp47_locales` holds comma separated language tags such as `ja-JP,en`, most preferred first.
+/// Returns null if no typeface has the character
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_manager_match_family_style_character(
+    font_manager: BorrowedPtr<FontMgr>,
+    name: BorrowedPtr<StringBox>,
+    weight: i32,
+    width: FontStyleWidth,
+    slant: Slant,
+    bcp47_locales: BorrowedPtr<StringBox>,
+    character: Unichar,
+) -> OwnedPtr<Typeface> {
+    font_manager
+        .with_clone(|font_manager| {
+            name.with_ref(|name| {
+                bcp47_locales.with_ref_ok(|bcp47_locales| {
+                    // the font manager expects the most preferred language last
+                    let bcp47_locales: Vec<&str> = bcp47_locales
+                        .as_str()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|locale| !locale.is_empty())
+                        .rev()
+                        .collect();
+                    match font_manager.match_family_style_character(
+                        name.as_str(),
+                        FontStyle::new(Weight::from(weight), width.into(), slant),
+                        &bcp47_locales,
+                        character,
+                    ) {
+                        None => OwnedPtr::null(),
+                        Some(typeface) => OwnedPtr::new(typeface),
+                    }
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the typeface closest to the family and style, or the default typeface
+/// if the family is null or not installed. Returns null if there are no fonts at all
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_font_manager_legacy_make_typeface(
+    font_manager: BorrowedPtr<FontMgr>,
+    name: BorrowedPtr<StringBox>, // may be null
+    weight: i32,
+    width: FontStyleWidth,
+    slant: Slant,
+) -> OwnedPtr<Typeface> {
+    font_manager
+        .with_clone(|font_manager| {
+            name.with_option_ref(|name| {
+                Ok(
+                    match font_manager.legacy_make_typeface(
+                        name.map(|name| name.as_str()),
+                        FontStyle::new(Weight::from(weight), width.into(), slant),
+                    ) {
+                        None => OwnedPtr::null(),
+                        Some(typeface) => OwnedPtr::new(typeface),
+                    },
+                )
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_font_manager_drop(font_manager: OwnedPtr<FontMgr>) {
     drop(font_manager);
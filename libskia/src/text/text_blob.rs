@@ -1,4 +1,9 @@
-use skia_safe::{Font, GlyphId, Point, TextBlob, TextBlobBuilder, TextEncoding};
+use array_box::ArrayBox;
+use skia_safe::utils::text_utils::Align;
+use skia_safe::{
+    ContourMeasure, ContourMeasureIter, Font, GlyphId, Paint, Path, Point, RSXform, Rect, TextBlob,
+    TextBlobBuilder, TextEncoding, scalar,
+};
 use string_box::StringBox;
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
@@ -44,7 +49,351 @@ pub extern "C" fn skia_text_blob_from_glyphs(
     .unwrap_or_default()
 }
 
+/// Creates a blob with the glyphs placed along the contours of the path and rotated to follow it.
+/// The text starts `offset` along the path and is aligned to that point.
+/// Glyphs whose center falls outside the path are left out, returns null if none remain
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_from_text_on_path(
+    text: BorrowedPtr<StringBox>,
+    font: BorrowedPtr<Font>,
+    path: BorrowedPtr<Path>,
+    offset: scalar,
+    align: Align,
+) -> OwnedPtr<TextBlob> {
+    text.with_ref(|text| {
+        font.with_ref(|font| {
+            path.with_ref_ok(
+                |path| match text_on_path(text.as_str(), font, path, offset, align) {
+                    None => OwnedPtr::null(),
+                    Some(text_blob) => OwnedPtr::new(text_blob),
+                },
+            )
+        })
+    })
+    .or_log(OwnedPtr::null())
+}
+
+fn text_on_path(
+    text: &str,
+    font: &Font,
+    path: &Path,
+    offset: scalar,
+    align: Align,
+) -> Option<TextBlob> {
+    let glyphs = font.str_to_glyphs_vec(text);
+    let mut widths = vec![0.0; glyphs.len()];
+    font.get_widths(&glyphs, &mut widths);
+    let mut xs = vec![0.0; glyphs.len()];
+    font.get_x_pos(&glyphs, &mut xs, None);
+
+    let text_width: scalar = widths.iter().sum();
+    let start = match align {
+        Align::Left => offset,
+        Align::Center => offset - text_width / 2.0,
+        Align::Right => offset - text_width,
+    };
+
+    let contours: Vec<ContourMeasure> = ContourMeasureIter::new(path, false, None).collect();
+    let mut placed_glyphs = vec![];
+    let mut xforms = vec![];
+    for ((glyph, x), width) in glyphs.iter().zip(xs).zip(widths) {
+        let half_width = width / 2.0;
+        let Some((center, tangent)) = pos_tan_along(&contours, start + x + half_width) else {
+            continue;
+        };
+        placed_glyphs.push(*glyph);
+        xforms.push(RSXform::new(
+            tangent.x,
+            tangent.y,
+            center - tangent * half_width,
+        ));
+    }
+    if placed_glyphs.is_empty() {
+        return None;
+    }
+
+    let mut text_blob_builder = TextBlobBuilder::new();
+    let (run_glyphs, run_xforms) = text_blob_builder.alloc_run_rsxform(font, placed_glyphs.len());
+    run_glyphs.copy_from_slice(&placed_glyphs);
+    run_xforms.copy_from_slice(&xforms);
+    text_blob_builder.make()
+}
+
+/// The point and unit tangent at a distance along the contours one after another
+fn pos_tan_along(contours: &[ContourMeasure], distance: scalar) -> Option<(Point, Point)> {
+    if distance < 0.0 {
+        return None;
+    }
+    let mut distance = distance;
+    for contour in contours {
+        if distance <= contour.length() {
+            return contour.pos_tan(distance);
+        }
+        distance -= contour.length();
+    }
+    None
+}
+
+/// Returns the conservative bounds of the glyphs relative to the blob origin
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_get_bounds(
+    text_blob: BorrowedPtr<TextBlob>,
+    mut bounds: BorrowedPtr<Rect>,
+) {
+    text_blob
+        .with_ref(|text_blob| {
+            bounds.with_mut_ok(|bounds| {
+                let blob_bounds = text_blob.bounds();
+                bounds.set_ltrb(
+                    blob_bounds.left,
+                    blob_bounds.top,
+                    blob_bounds.right,
+                    blob_bounds.bottom,
+                );
+            })
+        })
+        .log();
+}
+
+/// Writes pairs of horizontal start and end positions where the glyphs cross
+/// the band between `top` and `bottom`, for example to skip ink when underlining
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_get_intercepts(
+    text_blob: BorrowedPtr<TextBlob>,
+    top: scalar,
+    bottom: scalar,
+    paint: BorrowedPtr<Paint>, // may be null
+    mut intercepts: BorrowedPtr<ArrayBox<scalar>>,
+) {
+    text_blob
+        .with_ref(|text_blob| {
+            paint.with_option_ref(|paint| {
+                intercepts.with_mut_ok(|intercepts| {
+                    intercepts.set_vector(text_blob.get_intercepts([top, bottom], paint))
+                })
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_text_blob_drop(text_blob: OwnedPtr<TextBlob>) {
     drop(text_blob);
 }
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_new() -> OwnedPtr<TextBlobBuilder> {
+    OwnedPtr::new(TextBlobBuilder::new())
+}
+
+fn has_value_per_glyph(function: &str, glyphs: usize, name: &str, values: usize) -> bool {
+    if glyphs != values {
+        if cfg!(debug_assertions) {
+            eprintln!(
+                "[{}] Got {} glyphs but {} {}",
+                function, glyphs, values, name
+            )
+        };
+        return false;
+    }
+    true
+}
+
+/// Adds a run of glyphs at their horizontal positions on the baseline `y`.
+/// Returns false if there is not a position per glyph
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_alloc_run_pos_h(
+    mut text_blob_builder: BorrowedPtr<TextBlobBuilder>,
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    xs: BorrowedPtr<ArrayBox<scalar>>,
+    y: scalar,
+) -> bool {
+    text_blob_builder
+        .with_mut(|text_blob_builder| {
+            font.with_ref(|font| {
+                glyphs.with_ref(|glyphs| {
+                    xs.with_ref_ok(|xs| {
+                        if !has_value_per_glyph(
+                            "skia_text_blob_builder_alloc_run_pos_h",
+                            glyphs.length,
+                            "positions",
+                            xs.length,
+                        ) {
+                            return false;
+                        }
+                        let (run_glyphs, run_xs) =
+                            text_blob_builder.alloc_run_pos_h(font, glyphs.length, y, None);
+                        run_glyphs.copy_from_slice(glyphs.to_slice());
+                        run_xs.copy_from_slice(xs.to_slice());
+                        true
+                    })
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Adds a run of glyphs at their positions.
+/// Returns false if there is not a position per glyph
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_alloc_run_pos(
+    mut text_blob_builder: BorrowedPtr<TextBlobBuilder>,
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    positions: BorrowedPtr<ArrayBox<Point>>,
+) -> bool {
+    text_blob_builder
+        .with_mut(|text_blob_builder| {
+            font.with_ref(|font| {
+                glyphs.with_ref(|glyphs| {
+                    positions.with_ref_ok(|positions| {
+                        if !has_value_per_glyph(
+                            "skia_text_blob_builder_alloc_run_pos",
+                            glyphs.length,
+                            "positions",
+                            positions.length,
+                        ) {
+                            return false;
+                        }
+                        let (run_glyphs, run_positions) =
+                            text_blob_builder.alloc_run_pos(font, glyphs.length, None);
+                        run_glyphs.copy_from_slice(glyphs.to_slice());
+                        run_positions.copy_from_slice(positions.to_slice());
+                        true
+                    })
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Adds a run of individually rotated and scaled glyphs.
+/// `xforms` holds four values per glyph: the scaled cosine and sine of the rotation
+/// followed by the translation. Returns false if there is not a transform per glyph
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_alloc_run_rsxform(
+    mut text_blob_builder: BorrowedPtr<TextBlobBuilder>,
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    xforms: BorrowedPtr<ArrayBox<scalar>>,
+) -> bool {
+    text_blob_builder
+        .with_mut(|text_blob_builder| {
+            font.with_ref(|font| {
+                glyphs.with_ref(|glyphs| {
+                    xforms.with_ref_ok(|xforms| {
+                        if xforms.length % 4 != 0
+                            || !has_value_per_glyph(
+                                "skia_text_blob_builder_alloc_run_rsxform",
+                                glyphs.length,
+                                "transforms",
+                                xforms.length / 4,
+                            )
+                        {
+                            return false;
+                        }
+                        let (run_glyphs, run_xforms) =
+                            text_blob_builder.alloc_run_rsxform(font, glyphs.length);
+                        run_glyphs.copy_from_slice(glyphs.to_slice());
+                        for (run_xform, xform) in
+                            run_xforms.iter_mut().zip(xforms.to_slice().chunks_exact(4))
+                        {
+                            *run_xform = RSXform::new(xform[0], xform[1], (xform[2], xform[3]));
+                        }
+                        true
+                    })
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Adds a run of positioned glyphs together with the text they were shaped from,
+/// so that the text can be extracted, for example when exporting to PDF.
+/// `clusters` holds the byte offset in the UTF-8 text of each glyph.
+/// Returns false if there is not a position and a cluster per glyph or a cluster is out of the text
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_alloc_run_text_pos(
+    mut text_blob_builder: BorrowedPtr<TextBlobBuilder>,
+    font: BorrowedPtr<Font>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    positions: BorrowedPtr<ArrayBox<Point>>,
+    text: BorrowedPtr<StringBox>,
+    clusters: BorrowedPtr<ArrayBox<u32>>,
+) -> bool {
+    text_blob_builder
+        .with_mut(|text_blob_builder| {
+            font.with_ref(|font| {
+                glyphs.with_ref(|glyphs| {
+                    positions.with_ref(|positions| {
+                        text.with_ref(|text| {
+                            clusters.with_ref_ok(|clusters| {
+                                let function = "skia_text_blob_builder_alloc_run_text_pos";
+                                if !has_value_per_glyph(
+                                    function,
+                                    glyphs.length,
+                                    "positions",
+                                    positions.length,
+                                ) || !has_value_per_glyph(
+                                    function,
+                                    glyphs.length,
+                                    "clusters",
+                                    clusters.length,
+                                ) {
+                                    return false;
+                                }
+                                let text = text.as_str().as_bytes();
+                                let clusters = clusters.to_slice();
+                                if clusters
+                                    .iter()
+                                    .any(|cluster| *cluster as usize >= text.len())
+                                {
+                                    if cfg!(debug_assertions) {
+                                        eprintln!(
+                                            "[{}] Clusters must be offsets in the {} bytes of text",
+                                            function,
+                                            text.len()
+                                        )
+                                    };
+                                    return false;
+                                }
+                                let (run_glyphs, run_positions, run_text, run_clusters) =
+                                    text_blob_builder.alloc_run_text_pos(
+                                        font,
+                                        glyphs.length,
+                                        text.len(),
+                                        None,
+                                    );
+                                run_glyphs.copy_from_slice(glyphs.to_slice());
+                                run_positions.copy_from_slice(positions.to_slice());
+                                run_text.copy_from_slice(text);
+                                run_clusters.copy_from_slice(clusters);
+                                true
+                            })
+                        })
+                    })
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Creates a blob from the runs added so far and resets the builder.
+/// Returns null if no glyphs were added
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_make(
+    mut text_blob_builder: BorrowedPtr<TextBlobBuilder>,
+) -> OwnedPtr<TextBlob> {
+    text_blob_builder
+        .with_mut_ok(|text_blob_builder| match text_blob_builder.make() {
+            None => OwnedPtr::null(),
+            Some(text_blob) => OwnedPtr::new(text_blob),
+        })
+        .or_log(OwnedPtr::null())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_text_blob_builder_drop(text_blob_builder: OwnedPtr<TextBlobBuilder>) {
+    drop(text_blob_builder);
+}
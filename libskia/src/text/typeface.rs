@@ -1,8 +1,151 @@
+use array_box::ArrayBox;
+use skia_safe::font_arguments::VariationPosition;
+use skia_safe::font_arguments::variation_position::Coordinate;
+use skia_safe::font_parameters::VariationAxis;
 use skia_safe::font_style::{Slant, Weight, Width};
-use skia_safe::{FontMgr, FontStyle, Typeface};
+use skia_safe::typeface::SerializeBehavior;
+use skia_safe::{FontArguments, FontMgr, FontStyle, FourByteTag, GlyphId, Typeface};
 use string_box::StringBox;
 use value_box::{BorrowedPtr, OwnedPtr, ReturnBoxerResult};
 
+const FVAR_TABLE: FourByteTag = FourByteTag::from_chars('f', 'v', 'a', 'r');
+const NAME_TABLE: FourByteTag = FourByteTag::from_chars('n', 'a', 'm', 'e');
+
+/// Tables with color glyphs: layered vector glyphs, bitmap glyphs and SVG glyphs
+const COLOR_TABLES: [FourByteTag; 4] = [
+    FourByteTag::from_chars('C', 'O', 'L', 'R'),
+    FourByteTag::from_chars('C', 'B', 'D', 'T'),
+    FourByteTag::from_chars('s', 'b', 'i', 'x'),
+    FourByteTag::from_chars('S', 'V', 'G', ' '),
+];
+
+/// A predefined position in the design space of a variable font, such as "Bold Condensed"
+struct NamedInstance {
+    name_id: u16,
+    coordinates: Vec<Coordinate>,
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn variation_axes(typeface: &Typeface) -> Vec<VariationAxis> {
+    typeface.variation_design_parameters().unwrap_or_default()
+}
+
+/// Reads the named instances from the `fvar` table, skia does not expose them
+fn named_instances(typeface: &Typeface) -> Vec<NamedInstance> {
+    typeface
+        .copy_table_data(*FVAR_TABLE)
+        .and_then(|fvar| parse_named_instances(fvar.as_bytes()))
+        .unwrap_or_default()
+}
+
+fn parse_named_instances(fvar: &[u8]) -> Option<Vec<NamedInstance>> {
+    let axes_offset = read_u16(fvar, 4)? as usize;
+    let axis_count = read_u16(fvar, 8)? as usize;
+    let axis_size = read_u16(fvar, 10)? as usize;
+    let instance_count = read_u16(fvar, 12)? as usize;
+    let instance_size = read_u16(fvar, 14)? as usize;
+
+    let axis_tags = (0..axis_count)
+        .map(|axis| read_u32(fvar, axes_offset + axis * axis_size))
+        .collect::<Option<Vec<_>>>()?;
+
+    let instances_offset = axes_offset + axis_count * axis_size;
+    (0..instance_count)
+        .map(|instance| {
+            let offset = instances_offset + instance * instance_size;
+            let name_id = read_u16(fvar, offset)?;
+            // the coordinates are 16.16 fixed point numbers after the name id and the flags
+            let coordinates = axis_tags
+                .iter()
+                .enumerate()
+                .map(|(axis, tag)| {
+                    Some(Coordinate {
+                        axis: FourByteTag::new(*tag),
+                        value: read_u32(fvar, offset + 4 + axis * 4)? as i32 as f32 / 65536.0,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(NamedInstance {
+                name_id,
+                coordinates,
+            })
+        })
+        .collect()
+}
+
+/// Looks up a string in the `name` table, preferring the English Windows names
+fn font_name(typeface: &Typeface, name_id: u16) -> Option<String> {
+    let table = typeface.copy_table_data(*NAME_TABLE)?;
+    parse_font_name(table.as_bytes(), name_id)
+}
+
+fn parse_font_name(table: &[u8], name_id: u16) -> Option<String> {
+    let count = read_u16(table, 2)? as usize;
+    let storage_offset = read_u16(table, 4)? as usize;
+
+    let rank = |platform: u16, language: u16| match (platform, language) {
+        (3, 0x409) => Some(0),
+        (3, _) | (0, _) => Some(1),
+        (1, _) => Some(2),
+        _ => None,
+    };
+    let (platform, length, offset) = (0..count)
+        .filter_map(|record| {
+            let offset = 6 + record * 12;
+            let platform = read_u16(table, offset)?;
+            let language = read_u16(table, offset + 4)?;
+            if read_u16(table, offset + 6)? != name_id {
+                return None;
+            }
+            let length = read_u16(table, offset + 8)? as usize;
+            let string_offset = read_u16(table, offset + 10)? as usize;
+            Some((rank(platform, language)?, platform, length, string_offset))
+        })
+        .min_by_key(|(rank, ..)| *rank)
+        .map(|(_, platform, length, offset)| (platform, length, offset))?;
+
+    let bytes = table.get(storage_offset + offset..storage_offset + offset + length)?;
+    if platform == 1 {
+        // Mac Roman, only its ASCII subset is decoded
+        return bytes
+            .is_ascii()
+            .then(|| String::from_utf8_lossy(bytes).into_owned());
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+fn write_coordinates(
+    coordinates: &[Coordinate],
+    axis_tags: &mut ArrayBox<u32>,
+    values: &mut ArrayBox<f32>,
+) {
+    axis_tags.set_vector(
+        coordinates
+            .iter()
+            .map(|coordinate| *coordinate.axis)
+            .collect(),
+    );
+    values.set_vector(
+        coordinates
+            .iter()
+            .map(|coordinate| coordinate.value)
+            .collect(),
+    );
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_typeface_default() -> OwnedPtr<Typeface> {
     FontMgr::new()
@@ -81,7 +224,492 @@ pub extern "C" fn skia_typeface_is_fixed_pitch(typeface: BorrowedPtr<Typeface>)
         .or_log(false)
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_count_glyphs(typeface: BorrowedPtr<Typeface>) -> usize {
+    typeface
+        .with_ref_ok(|typeface| typeface.count_glyphs())
+        .or_log(0)
+}
+
+/// Returns the amount of font units per em, 0 if it is unknown
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_units_per_em(typeface: BorrowedPtr<Typeface>) -> i32 {
+    typeface
+        .with_ref_ok(|typeface| typeface.units_per_em().unwrap_or(0))
+        .or_log(0)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_count_tables(typeface: BorrowedPtr<Typeface>) -> usize {
+    typeface
+        .with_ref_ok(|typeface| typeface.count_tables())
+        .or_log(0)
+}
+
+/// Writes the four-byte tags of the font tables, such as `name` and `OS/2`
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_table_tags(
+    typeface: BorrowedPtr<Typeface>,
+    mut tags: BorrowedPtr<ArrayBox<u32>>,
+) {
+    typeface
+        .with_ref(|typeface| {
+            tags.with_mut_ok(|tags| tags.set_vector(typeface.table_tags().unwrap_or_default()))
+        })
+        .log();
+}
+
+/// Writes the raw bytes of the table with the tag, returns false if there is no such table
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_copy_table_data(
+    typeface: BorrowedPtr<Typeface>,
+    tag: u32,
+    mut data: BorrowedPtr<ArrayBox<u8>>,
+) -> bool {
+    typeface
+        .with_ref(|typeface| {
+            data.with_mut_ok(|data| match typeface.copy_table_data(tag) {
+                None => false,
+                Some(table) => {
+                    data.set_array(table.as_bytes());
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+/// Returns the amount of family names in all the languages of the typeface
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_localized_family_name_count(
+    typeface: BorrowedPtr<Typeface>,
+) -> usize {
+    typeface
+        .with_ref_ok(|typeface| typeface.new_family_name_iterator().count())
+        .or_log(0)
+}
+
+/// Writes the family name at `index` and its BCP 47 language tag.
+/// Returns false if there is no such name
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_localized_family_name(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+    mut name: BorrowedPtr<StringBox>,
+    mut language: BorrowedPtr<StringBox>,
+) -> bool {
+    typeface
+        .with_ref(|typeface| {
+            name.with_mut(|name| {
+                language.with_mut_ok(|language| {
+                    match typeface.new_family_name_iterator().nth(index) {
+                        None => false,
+                        Some(localized_name) => {
+                            name.set_string(localized_name.string);
+                            language.set_string(localized_name.language);
+                            true
+                        }
+                    }
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Returns false if the typeface has no PostScript name
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_post_script_name(
+    typeface: BorrowedPtr<Typeface>,
+    mut name: BorrowedPtr<StringBox>,
+) -> bool {
+    typeface
+        .with_ref(|typeface| {
+            name.with_mut_ok(|name| match typeface.post_script_name() {
+                None => false,
+                Some(post_script_name) => {
+                    name.set_string(post_script_name);
+                    true
+                }
+            })
+        })
+        .or_log(false)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_serialize(
+    typeface: BorrowedPtr<Typeface>,
+    behavior: SerializeBehavior,
+    mut data: BorrowedPtr<ArrayBox<u8>>,
+) {
+    typeface
+        .with_ref(|typeface| {
+            data.with_mut_ok(|data| data.set_array(typeface.serialize(behavior).as_bytes()))
+        })
+        .log();
+}
+
+/// Creates a typeface from the data written by [`skia_typeface_serialize`].
+/// Typefaces serialized without their font data are matched with `font_mgr`
+/// or with the default font manager if it is null
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_deserialize(
+    data: BorrowedPtr<ArrayBox<u8>>,
+    font_mgr: BorrowedPtr<FontMgr>, // may be null
+) -> OwnedPtr<Typeface> {
+    data.with_ref(|data| {
+        font_mgr.with_option_ref(|font_mgr| {
+            let font_mgr = font_mgr.cloned().unwrap_or_else(FontMgr::new);
+            Ok(
+                match Typeface::make_deserialize(data.to_slice(), font_mgr) {
+                    None => OwnedPtr::null(),
+                    Some(typeface) => OwnedPtr::new(typeface),
+                },
+            )
+        })
+    })
+    .or_log(OwnedPtr::null())
+}
+
+/// Returns true if the typeface has color glyphs, such as emoji
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_is_a_color_font(typeface: BorrowedPtr<Typeface>) -> bool {
+    typeface
+        .with_ref_ok(|typeface| {
+            COLOR_TABLES
+                .iter()
+                .any(|tag| typeface.get_table_size(**tag).is_some())
+        })
+        .or_log(false)
+}
+
+/// Writes the kerning adjustments in font units between each pair of consecutive glyphs.
+/// Returns false if the typeface has no kerning information or needs shaping for it
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_kerning_pair_adjustments(
+    typeface: BorrowedPtr<Typeface>,
+    glyphs: BorrowedPtr<ArrayBox<GlyphId>>,
+    mut adjustments: BorrowedPtr<ArrayBox<i32>>,
+) -> bool {
+    typeface
+        .with_ref(|typeface| {
+            glyphs.with_ref(|glyphs| {
+                adjustments.with_mut_ok(|adjustments| {
+                    let glyphs = glyphs.to_slice();
+                    let mut pair_adjustments = vec![0; glyphs.len().saturating_sub(1)];
+                    let has_kerning = !glyphs.is_empty()
+                        && typeface.get_kerning_pair_adjustments(glyphs, &mut pair_adjustments);
+                    adjustments.set_vector(if has_kerning {
+                        pair_adjustments
+                    } else {
+                        vec![]
+                    });
+                    has_kerning
+                })
+            })
+        })
+        .or_log(false)
+}
+
+/// Returns the amount of variation axes, 0 if the typeface is not variable
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_variation_axis_count(typeface: BorrowedPtr<Typeface>) -> usize {
+    typeface
+        .with_ref_ok(|typeface| variation_axes(typeface).len())
+        .or_log(0)
+}
+
+fn with_variation_axis<T>(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+    default: T,
+    f: impl FnOnce(&VariationAxis) -> T,
+) -> T
+where
+    T: Copy + 'static,
+{
+    typeface
+        .with_ref_ok(|typeface| match variation_axes(typeface).get(index) {
+            None => default,
+            Some(axis) => f(axis),
+        })
+        .or_log(default)
+}
+
+/// Returns the four-byte tag of the axis at `index`, such as `wght`, or 0 if there is no such axis
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_variation_axis_tag(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+) -> u32 {
+    with_variation_axis(typeface, index, 0, |axis| *axis.tag)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_variation_axis_min(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+) -> f32 {
+    with_variation_axis(typeface, index, 0.0, |axis| axis.min)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_variation_axis_default(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+) -> f32 {
+    with_variation_axis(typeface, index, 0.0, |axis| axis.def)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_variation_axis_max(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+) -> f32 {
+    with_variation_axis(typeface, index, 0.0, |axis| axis.max)
+}
+
+/// Hidden axes are not meant to be shown in user interfaces
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_is_variation_axis_hidden(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+) -> bool {
+    with_variation_axis(typeface, index, false, |axis| axis.is_hidden())
+}
+
+/// Writes the current position of the typeface in the design space as pairs of
+/// axis tags and values, nothing if the typeface is not variable
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_variation_design_position(
+    typeface: BorrowedPtr<Typeface>,
+    mut axis_tags: BorrowedPtr<ArrayBox<u32>>,
+    mut values: BorrowedPtr<ArrayBox<f32>>,
+) {
+    typeface
+        .with_ref(|typeface| {
+            axis_tags.with_mut(|axis_tags| {
+                values.with_mut_ok(|values| {
+                    let coordinates = typeface.variation_design_position().unwrap_or_default();
+                    write_coordinates(&coordinates, axis_tags, values)
+                })
+            })
+        })
+        .log();
+}
+
+/// Sets the axes with the tags to the values, there must be a value per tag
+pub(crate) fn clone_with_variations(
+    typeface: &Typeface,
+    axis_tags: &[u32],
+    values: &[f32],
+) -> Option<Typeface> {
+    let coordinates: Vec<Coordinate> = axis_tags
+        .iter()
+        .zip(values)
+        .map(|(tag, value)| Coordinate {
+            axis: FourByteTag::new(*tag),
+            value: *value,
+        })
+        .collect();
+    let arguments = FontArguments::new().set_variation_design_position(VariationPosition {
+        coordinates: &coordinates,
+    });
+    typeface.clone_with_arguments(&arguments)
+}
+
+/// Returns a typeface with the axes set to the values, the other axes keep their position.
+/// Returns null if the amounts of tags and values differ
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_clone_with_variations(
+    typeface: BorrowedPtr<Typeface>,
+    axis_tags: BorrowedPtr<ArrayBox<u32>>,
+    values: BorrowedPtr<ArrayBox<f32>>,
+) -> OwnedPtr<Typeface> {
+    typeface
+        .with_ref(|typeface| {
+            axis_tags.with_ref(|axis_tags| {
+                values.with_ref_ok(|values| {
+                    let (axis_tags, values) = (axis_tags.to_slice(), values.to_slice());
+                    if axis_tags.len() != values.len() {
+                        if cfg!(debug_assertions) {
+                            eprintln!(
+                                "[skia_typeface_clone_with_variations] Got {} axis tags but {} values",
+                                axis_tags.len(),
+                                values.len()
+                            );
+                        };
+                        return OwnedPtr::null();
+                    }
+                    match clone_with_variations(typeface, axis_tags, values) {
+                        None => OwnedPtr::null(),
+                        Some(typeface) => OwnedPtr::new(typeface),
+                    }
+                })
+            })
+        })
+        .or_log(OwnedPtr::null())
+}
+
+/// Returns the amount of named instances of a variable typeface, such as "Bold Condensed"
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_named_instance_count(typeface: BorrowedPtr<Typeface>) -> usize {
+    typeface
+        .with_ref_ok(|typeface| named_instances(typeface).len())
+        .or_log(0)
+}
+
+/// Writes the name of the named instance at `index`, empty if it has no name
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_named_instance_name(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+    mut name: BorrowedPtr<StringBox>,
+) {
+    typeface
+        .with_ref(|typeface| {
+            name.with_mut_ok(|name| {
+                let instance_name = named_instances(typeface)
+                    .get(index)
+                    .and_then(|instance| font_name(typeface, instance.name_id));
+                name.set_string(instance_name.unwrap_or_default())
+            })
+        })
+        .log();
+}
+
+/// Writes the position of the named instance at `index` as pairs of axis tags and values,
+/// which can be passed to [`skia_typeface_clone_with_variations`]
+#[unsafe(no_mangle)]
+pub extern "C" fn skia_typeface_get_named_instance_coordinates(
+    typeface: BorrowedPtr<Typeface>,
+    index: usize,
+    mut axis_tags: BorrowedPtr<ArrayBox<u32>>,
+    mut values: BorrowedPtr<ArrayBox<f32>>,
+) {
+    typeface
+        .with_ref(|typeface| {
+            axis_tags.with_mut(|axis_tags| {
+                values.with_mut_ok(|values| {
+                    let coordinates = named_instances(typeface)
+                        .into_iter()
+                        .nth(index)
+                        .map(|instance| instance.coordinates)
+                        .unwrap_or_default();
+                    write_coordinates(&coordinates, axis_tags, values)
+                })
+            })
+        })
+        .log();
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn skia_typeface_drop(typeface: OwnedPtr<Typeface>) {
     drop(typeface);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed(value: f32) -> [u8; 4] {
+        ((value * 65536.0) as i32).to_be_bytes()
+    }
+
+    /// An `fvar` table with the weight and slant axes and two named instances
+    fn fvar() -> Vec<u8> {
+        let mut fvar = vec![];
+        // version 1.0, axes at 16, reserved, 2 axes of 20 bytes, 2 instances of 12 bytes
+        for value in [1u16, 0, 16, 2, 2, 20, 2, 12] {
+            fvar.extend(value.to_be_bytes());
+        }
+        for (tag, min, default, max) in [(b"wght", 100.0, 400.0, 900.0), (b"slnt", -20.0, 0.0, 0.0)]
+        {
+            fvar.extend(tag);
+            fvar.extend(fixed(min));
+            fvar.extend(fixed(default));
+            fvar.extend(fixed(max));
+            fvar.extend([0, 0, 1, 0]);
+        }
+        for (name_id, weight, slant) in [(256u16, 700.0, 0.0), (257, 300.0, -12.5)] {
+            fvar.extend(name_id.to_be_bytes());
+            fvar.extend([0, 0]);
+            fvar.extend(fixed(weight));
+            fvar.extend(fixed(slant));
+        }
+        fvar
+    }
+
+    /// A `name` table with the records and their strings, encoded like their platform does
+    fn name(records: &[(u16, u16, u16, &str)]) -> Vec<u8> {
+        let mut table = vec![];
+        let mut storage = vec![];
+        for value in [0, records.len() as u16, 6 + 12 * records.len() as u16] {
+            table.extend(value.to_be_bytes());
+        }
+        for (platform, language, name_id, string) in records {
+            let bytes: Vec<u8> = match *platform {
+                1 => string.bytes().collect(),
+                _ => string.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            };
+            let encoding = if *platform == 1 { 0 } else { 1 };
+            for value in [
+                *platform,
+                encoding,
+                *language,
+                *name_id,
+                bytes.len() as u16,
+                storage.len() as u16,
+            ] {
+                table.extend(value.to_be_bytes());
+            }
+            storage.extend(bytes);
+        }
+        table.extend(storage);
+        table
+    }
+
+    #[test]
+    fn parse_named_instances_of_fvar() {
+        let instances = parse_named_instances(&fvar()).unwrap();
+        assert_eq!(instances.len(), 2);
+
+        let coordinates = |instance: &NamedInstance| {
+            instance
+                .coordinates
+                .iter()
+                .map(|coordinate| (*coordinate.axis, coordinate.value))
+                .collect::<Vec<_>>()
+        };
+        let (weight, slant) = (
+            *FourByteTag::from_chars('w', 'g', 'h', 't'),
+            *FourByteTag::from_chars('s', 'l', 'n', 't'),
+        );
+        assert_eq!(instances[0].name_id, 256);
+        assert_eq!(coordinates(&instances[0]), [(weight, 700.0), (slant, 0.0)]);
+        assert_eq!(instances[1].name_id, 257);
+        assert_eq!(
+            coordinates(&instances[1]),
+            [(weight, 300.0), (slant, -12.5)]
+        );
+
+        // a truncated table has no instances
+        let fvar = fvar();
+        assert!(parse_named_instances(&fvar[..fvar.len() - 2]).is_none());
+    }
+
+    #[test]
+    fn parse_font_name_prefers_english_windows_names() {
+        let table = name(&[
+            (1, 0, 256, "Bold (Mac)"),
+            (3, 0x407, 256, "Fett"),
+            (3, 0x409, 256, "Bold"),
+            (1, 0, 257, "Light"),
+        ]);
+        assert_eq!(parse_font_name(&table, 256).as_deref(), Some("Bold"));
+        assert_eq!(parse_font_name(&table, 257).as_deref(), Some("Light"));
+        assert_eq!(parse_font_name(&table, 258), None);
+
+        let table = name(&[(1, 0, 256, "Bold (Mac)"), (3, 0x407, 256, "Fett")]);
+        assert_eq!(parse_font_name(&table, 256).as_deref(), Some("Fett"));
+    }
+}
@@ -0,0 +1,38 @@
+use Skia::bitmap::{
+    skia_bitmap_alloc_pixels, skia_bitmap_drop, skia_bitmap_erase, skia_bitmap_get_color,
+    skia_bitmap_new,
+};
+use skia_safe::{AlphaType, Bitmap, Color, ColorType, ISize, ImageInfo};
+use value_box::BorrowedPtr;
+
+#[test]
+fn bitmap_erase_and_get_color() {
+    let mut bitmap = Bitmap::new();
+    let image_info = ImageInfo::new(
+        ISize::new(4, 4),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    );
+    let color = Color::RED;
+
+    assert!(skia_bitmap_alloc_pixels(
+        BorrowedPtr::from_mut(&mut bitmap),
+        BorrowedPtr::from_ref(&image_info)
+    ));
+    skia_bitmap_erase(
+        BorrowedPtr::from_ref(&bitmap),
+        BorrowedPtr::from_ref(&color),
+        BorrowedPtr::null(),
+    );
+
+    skia_bitmap_get_color(BorrowedPtr::from_ref(&bitmap), 2, 3)
+        .with_value_ok(|pixel| assert_eq!(pixel, Color::RED))
+        .unwrap();
+    assert!(skia_bitmap_get_color(BorrowedPtr::from_ref(&bitmap), 4, 0).is_null());
+}
+
+#[test]
+fn bitmap_drop_accepts_owned_pointer() {
+    skia_bitmap_drop(skia_bitmap_new());
+}
@@ -0,0 +1,73 @@
+use Skia::bounds_canvas::{
+    skia_bounds_canvas_get_bounds, skia_bounds_canvas_get_canvas, skia_bounds_canvas_new,
+};
+use skia_safe::{Color, Paint, Rect, surfaces};
+use value_box::BorrowedPtr;
+
+#[test]
+fn bounds_canvas_tracks_device_bounds() {
+    let mut bounds_canvas = skia_bounds_canvas_new()
+        .with_value_ok(|bounds_canvas| bounds_canvas)
+        .unwrap();
+
+    skia_bounds_canvas_get_canvas(BorrowedPtr::from_mut(&mut bounds_canvas))
+        .with_ref_ok(|canvas| {
+            let paint = Paint::default();
+            canvas.translate((100.0, 50.0));
+            canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &paint);
+            canvas.scale((2.0, 2.0));
+            canvas.draw_rect(Rect::new(-20.0, 0.0, 0.0, 5.0), &paint);
+        })
+        .unwrap();
+
+    skia_bounds_canvas_get_bounds(BorrowedPtr::from_mut(&mut bounds_canvas))
+        .with_value_ok(|bounds| assert_eq!(bounds, Rect::new(60.0, 50.0, 110.0, 60.0)))
+        .unwrap();
+    assert!(skia_bounds_canvas_get_canvas(BorrowedPtr::from_mut(&mut bounds_canvas)).is_null());
+}
+
+#[test]
+fn bounds_canvas_without_draws() {
+    let mut bounds_canvas = skia_bounds_canvas_new()
+        .with_value_ok(|bounds_canvas| bounds_canvas)
+        .unwrap();
+    assert!(skia_bounds_canvas_get_bounds(BorrowedPtr::from_mut(&mut bounds_canvas)).is_null());
+}
+
+#[test]
+fn bounds_canvas_tracks_images() {
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    surface.canvas().clear(Color::RED);
+    let image = surface.image_snapshot();
+
+    let mut bounds_canvas = skia_bounds_canvas_new()
+        .with_value_ok(|bounds_canvas| bounds_canvas)
+        .unwrap();
+    skia_bounds_canvas_get_canvas(BorrowedPtr::from_mut(&mut bounds_canvas))
+        .with_ref_ok(|canvas| {
+            canvas.draw_image(&image, (20.0, 30.0), None);
+        })
+        .unwrap();
+
+    skia_bounds_canvas_get_bounds(BorrowedPtr::from_mut(&mut bounds_canvas))
+        .with_value_ok(|bounds| assert_eq!(bounds, Rect::new(20.0, 30.0, 30.0, 40.0)))
+        .unwrap();
+}
+
+#[test]
+fn bounds_canvas_includes_stroke_outsets() {
+    let mut bounds_canvas = skia_bounds_canvas_new()
+        .with_value_ok(|bounds_canvas| bounds_canvas)
+        .unwrap();
+    skia_bounds_canvas_get_canvas(BorrowedPtr::from_mut(&mut bounds_canvas))
+        .with_ref_ok(|canvas| {
+            let mut paint = Paint::default();
+            paint.set_stroke(true).set_stroke_width(4.0);
+            canvas.draw_rect(Rect::new(10.0, 10.0, 20.0, 20.0), &paint);
+        })
+        .unwrap();
+
+    skia_bounds_canvas_get_bounds(BorrowedPtr::from_mut(&mut bounds_canvas))
+        .with_value_ok(|bounds| assert!(bounds.contains(Rect::new(8.0, 8.0, 22.0, 22.0))))
+        .unwrap();
+}
@@ -0,0 +1,91 @@
+use Skia::canvas_clip::{
+    skia_canvas_clip_shader, skia_canvas_get_device_clip_as_region, skia_canvas_is_clip_empty,
+    skia_canvas_is_clip_rect,
+};
+use skia_safe::gradient_shader::{Flags, GradientShaderColors};
+use skia_safe::{ClipOp, Color, IRect, Point, Rect, Region, Shader, TileMode, surfaces};
+use value_box::BorrowedPtr;
+
+#[test]
+fn device_clip_of_rectangular_clip_is_exact() {
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.translate((10.0, 10.0));
+    canvas.clip_rect(Rect::new(0.0, 0.0, 20.0, 30.0), ClipOp::Intersect, false);
+
+    assert!(skia_canvas_is_clip_rect(BorrowedPtr::from_ref(canvas)));
+    assert!(!skia_canvas_is_clip_empty(BorrowedPtr::from_ref(canvas)));
+
+    let mut region = Region::new();
+    assert!(skia_canvas_get_device_clip_as_region(
+        BorrowedPtr::from_ref(canvas),
+        BorrowedPtr::from_mut(&mut region)
+    ));
+    assert_eq!(*region.bounds(), IRect::new(10, 10, 30, 40));
+}
+
+#[test]
+fn device_clip_of_complex_clip_is_approximated() {
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clip_rect(Rect::new(0.0, 0.0, 20.0, 20.0), ClipOp::Intersect, false);
+    canvas.clip_rect(Rect::new(5.0, 5.0, 10.0, 10.0), ClipOp::Difference, false);
+
+    assert!(!skia_canvas_is_clip_rect(BorrowedPtr::from_ref(canvas)));
+
+    let mut region = Region::new();
+    assert!(!skia_canvas_get_device_clip_as_region(
+        BorrowedPtr::from_ref(canvas),
+        BorrowedPtr::from_mut(&mut region)
+    ));
+    assert_eq!(*region.bounds(), IRect::new(0, 0, 20, 20));
+}
+
+#[test]
+fn device_clip_of_empty_clip() {
+    let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clip_rect(Rect::new(0.0, 0.0, 0.0, 0.0), ClipOp::Intersect, false);
+
+    assert!(skia_canvas_is_clip_empty(BorrowedPtr::from_ref(canvas)));
+
+    let mut region = Region::from_rect(IRect::new(0, 0, 1, 1));
+    assert!(skia_canvas_get_device_clip_as_region(
+        BorrowedPtr::from_ref(canvas),
+        BorrowedPtr::from_mut(&mut region)
+    ));
+    assert!(region.is_empty());
+}
+
+#[test]
+fn clip_shader_fades_with_the_gradient() {
+    let mut surface = surfaces::raster_n32_premul((100, 10)).unwrap();
+    surface.canvas().clear(Color::TRANSPARENT);
+
+    // from opaque on the left to transparent on the right
+    let colors = [Color::BLACK, Color::TRANSPARENT];
+    let shader = Shader::linear_gradient(
+        (Point::new(0.0, 0.0), Point::new(100.0, 0.0)),
+        GradientShaderColors::Colors(&colors),
+        None,
+        TileMode::Clamp,
+        Flags::empty(),
+        None,
+    )
+    .unwrap();
+
+    let canvas = surface.canvas();
+    skia_canvas_clip_shader(
+        BorrowedPtr::from_ref(canvas),
+        BorrowedPtr::from_ref(&shader),
+        ClipOp::Intersect,
+    );
+    canvas.clear(Color::RED);
+
+    let pixels = surface.peek_pixels().unwrap();
+    let alpha = |x: i32| pixels.get_color((x, 5)).a();
+    assert!(alpha(1) > 0xF0);
+    assert!((0x70..=0x90).contains(&alpha(50)));
+    assert!(alpha(98) < 0x10);
+    assert!(alpha(10) > alpha(40) && alpha(40) > alpha(70));
+}
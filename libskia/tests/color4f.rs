@@ -0,0 +1,69 @@
+use Skia::color::{skia_color_hsv_to_color, skia_color_rgb_to_hsv};
+use Skia::color4f::{
+    skia_color4f_array_create_with, skia_color4f_create, skia_color4f_drop,
+    skia_color4f_from_color, skia_color4f_premul, skia_color4f_to_color, skia_color4f_unpremul,
+};
+use array_box::ArrayBox;
+use skia_safe::{Color, Color4f};
+use value_box::BorrowedPtr;
+
+#[test]
+fn color4f_color_round_trip() {
+    let color = Color::from_argb(255, 255, 0, 0);
+    let color4f = skia_color4f_from_color(BorrowedPtr::from_ref(&color));
+    color4f
+        .with_value_ok(|color4f| {
+            assert_eq!(color4f, Color4f::new(1.0, 0.0, 0.0, 1.0));
+            skia_color4f_to_color(BorrowedPtr::from_ref(&color4f))
+                .with_value_ok(|converted| assert_eq!(converted, color))
+                .unwrap();
+        })
+        .unwrap();
+}
+
+#[test]
+fn color4f_premul_and_unpremul() {
+    let color = Color4f::new(1.0, 0.5, 0.25, 0.5);
+    let premultiplied = skia_color4f_premul(BorrowedPtr::from_ref(&color))
+        .with_value_ok(|premultiplied| premultiplied)
+        .unwrap();
+    assert_eq!(premultiplied, Color4f::new(0.5, 0.25, 0.125, 0.5));
+
+    skia_color4f_unpremul(BorrowedPtr::from_ref(&premultiplied))
+        .with_value_ok(|unpremultiplied| assert_eq!(unpremultiplied, color))
+        .unwrap();
+
+    let transparent = Color4f::new(1.0, 1.0, 1.0, 0.0);
+    skia_color4f_unpremul(BorrowedPtr::from_ref(&transparent))
+        .with_value_ok(|unpremultiplied| {
+            assert_eq!(unpremultiplied, Color4f::new(0.0, 0.0, 0.0, 0.0))
+        })
+        .unwrap();
+}
+
+#[test]
+fn color4f_array() {
+    let color = Color4f::new(0.1, 0.2, 0.3, 0.4);
+    skia_color4f_array_create_with(BorrowedPtr::from_ref(&color), 3)
+        .with_value_ok(|array| {
+            assert_eq!(array.length, 3);
+            assert_eq!(array.at(2), color);
+        })
+        .unwrap();
+}
+
+#[test]
+fn color_hsv_round_trip() {
+    let mut hsv = ArrayBox::<f32>::new();
+    skia_color_rgb_to_hsv(0, 0, 255, BorrowedPtr::from_mut(&mut hsv));
+    assert_eq!(hsv.to_slice(), &[240.0, 1.0, 1.0]);
+
+    skia_color_hsv_to_color(240.0, 1.0, 1.0, 255)
+        .with_value_ok(|color| assert_eq!(color, Color::BLUE))
+        .unwrap();
+}
+
+#[test]
+fn color4f_drop_accepts_owned_pointer() {
+    skia_color4f_drop(skia_color4f_create(0.0, 0.0, 0.0, 1.0));
+}
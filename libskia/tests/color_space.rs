@@ -1,6 +1,12 @@
 use Skia::color_space::{
-    skia_color_space_drop, skia_color_space_is_srgb, skia_color_space_new_srgb,
+    ColorSpaceGamut, ColorSpaceTransferFunction, skia_color_space_deserialize,
+    skia_color_space_drop, skia_color_space_equals, skia_color_space_gamma_is_linear,
+    skia_color_space_get_transfer_fn, skia_color_space_is_srgb, skia_color_space_new_from_icc,
+    skia_color_space_new_rgb, skia_color_space_new_rgb_with_primaries, skia_color_space_new_srgb,
+    skia_color_space_new_srgb_linear, skia_color_space_serialize, skia_color_space_to_icc,
+    skia_color_space_to_xyz_d50,
 };
+use array_box::ArrayBox;
 use skia_safe::ColorSpace;
 use value_box::{BorrowedPtr, OwnedPtr};
 
@@ -22,6 +28,191 @@ fn color_space_is_srgb_for_null() {
     assert!(!skia_color_space_is_srgb(color_space));
 }
 
+#[test]
+fn color_space_new_srgb_linear_has_linear_gamma() {
+    let color_space = ColorSpace::new_srgb_linear();
+    assert!(skia_color_space_gamma_is_linear(BorrowedPtr::from_ref(
+        &color_space
+    )));
+    assert!(!skia_color_space_gamma_is_linear(BorrowedPtr::from_ref(
+        &ColorSpace::new_srgb()
+    )));
+    skia_color_space_drop(skia_color_space_new_srgb_linear());
+}
+
+#[test]
+fn color_space_new_rgb_with_named_gamut() {
+    let srgb = ColorSpace::new_srgb();
+    skia_color_space_new_rgb(ColorSpaceTransferFunction::Srgb, ColorSpaceGamut::Srgb)
+        .with_value_ok(|color_space| {
+            assert!(color_space.is_srgb());
+            assert!(skia_color_space_equals(
+                BorrowedPtr::from_ref(&color_space),
+                BorrowedPtr::from_ref(&srgb)
+            ));
+        })
+        .unwrap();
+
+    skia_color_space_new_rgb(ColorSpaceTransferFunction::Linear, ColorSpaceGamut::Srgb)
+        .with_value_ok(|color_space| {
+            assert_eq!(color_space, ColorSpace::new_srgb_linear());
+        })
+        .unwrap();
+
+    skia_color_space_new_rgb(ColorSpaceTransferFunction::Srgb, ColorSpaceGamut::DisplayP3)
+        .with_value_ok(|color_space| {
+            assert!(!color_space.is_srgb());
+            assert!(!color_space.with_linear_gamma().is_srgb());
+        })
+        .unwrap();
+
+    // skia has no named Adobe RGB or XYZ gamut
+    assert!(
+        skia_color_space_new_rgb(ColorSpaceTransferFunction::Srgb, ColorSpaceGamut::AdobeRgb)
+            .is_null()
+    );
+    assert!(
+        skia_color_space_new_rgb(ColorSpaceTransferFunction::Linear, ColorSpaceGamut::Xyz)
+            .is_null()
+    );
+}
+
+#[test]
+fn color_space_new_rgb_with_primaries() {
+    // the Display P3 primaries
+    let display_p3 = skia_color_space_new_rgb_with_primaries(
+        ColorSpaceTransferFunction::Linear,
+        0.680,
+        0.320,
+        0.265,
+        0.690,
+        0.150,
+        0.060,
+        0.3127,
+        0.3290,
+    )
+    .with_value_ok(|color_space| color_space)
+    .unwrap();
+    skia_color_space_new_rgb(
+        ColorSpaceTransferFunction::Linear,
+        ColorSpaceGamut::DisplayP3,
+    )
+    .with_value_ok(|color_space| assert_eq!(color_space, display_p3))
+    .unwrap();
+
+    let invalid = skia_color_space_new_rgb_with_primaries(
+        ColorSpaceTransferFunction::Linear,
+        0.3,
+        0.3,
+        0.3,
+        0.3,
+        0.3,
+        0.3,
+        0.3127,
+        0.3290,
+    );
+    assert!(invalid.is_null());
+}
+
+#[test]
+fn color_space_icc_round_trip() {
+    let color_space = ColorSpace::new_srgb_linear();
+    let mut icc = ArrayBox::<u8>::new();
+    assert!(skia_color_space_to_icc(
+        BorrowedPtr::from_ref(&color_space),
+        BorrowedPtr::from_mut(&mut icc)
+    ));
+
+    skia_color_space_new_from_icc(BorrowedPtr::from_ref(&icc))
+        .with_value_ok(|parsed| {
+            assert!(skia_color_space_gamma_is_linear(BorrowedPtr::from_ref(
+                &parsed
+            )));
+        })
+        .unwrap();
+}
+
+#[test]
+fn color_space_to_xyz_d50() {
+    let color_space = ColorSpace::new_srgb();
+    let mut matrix = ArrayBox::<f32>::new();
+    assert!(skia_color_space_to_xyz_d50(
+        BorrowedPtr::from_ref(&color_space),
+        BorrowedPtr::from_mut(&mut matrix)
+    ));
+    let matrix = matrix.to_slice();
+    assert_eq!(matrix.len(), 9);
+    // the luminance row of the sRGB gamut sums up to the luminance of the white point
+    assert!((matrix[3] + matrix[4] + matrix[5] - 1.0).abs() < 0.01);
+}
+
+#[test]
+fn color_space_serialize_and_deserialize() {
+    let color_space = ColorSpace::new_srgb_linear();
+    let mut data = ArrayBox::<u8>::new();
+    skia_color_space_serialize(
+        BorrowedPtr::from_ref(&color_space),
+        BorrowedPtr::from_mut(&mut data),
+    );
+
+    skia_color_space_deserialize(BorrowedPtr::from_ref(&data))
+        .with_value_ok(|deserialized| {
+            assert!(skia_color_space_equals(
+                BorrowedPtr::from_ref(&deserialized),
+                BorrowedPtr::from_ref(&color_space)
+            ));
+        })
+        .unwrap();
+
+    let garbage = ArrayBox::from_vector(vec![1u8, 2, 3]);
+    assert!(skia_color_space_deserialize(BorrowedPtr::from_ref(&garbage)).is_null());
+}
+
+fn serialized_with_transfer_fn(transfer_fn: [f32; 7]) -> ArrayBox<u8> {
+    let mut bytes = ColorSpace::new_srgb().serialize().as_bytes().to_vec();
+    for (index, value) in transfer_fn.iter().enumerate() {
+        let offset = 4 + index * 4;
+        bytes[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+    }
+    ArrayBox::from_vector(bytes)
+}
+
+#[test]
+fn color_space_deserialize_rejects_invalid_transfer_fn() {
+    // g, a, b, c, d, e, f
+    let valid = serialized_with_transfer_fn([1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    assert!(!skia_color_space_deserialize(BorrowedPtr::from_ref(&valid)).is_null());
+
+    // a negative base for the power function
+    let negative_base = serialized_with_transfer_fn([1.0, 1.0, -2.0, 0.0, 1.0, 0.0, 0.0]);
+    assert!(skia_color_space_deserialize(BorrowedPtr::from_ref(&negative_base)).is_null());
+
+    // finite coefficients whose sum overflows
+    let overflowing_sum =
+        serialized_with_transfer_fn([1.0, f32::MAX, f32::MAX, 0.0, 0.0, 0.0, 0.0]);
+    assert!(skia_color_space_deserialize(BorrowedPtr::from_ref(&overflowing_sum)).is_null());
+
+    let fractional_pq_tag = serialized_with_transfer_fn([-2.5, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    assert!(skia_color_space_deserialize(BorrowedPtr::from_ref(&fractional_pq_tag)).is_null());
+}
+
+#[test]
+fn color_space_new_from_invalid_icc() {
+    let garbage = ArrayBox::from_vector(vec![0u8; 128]);
+    assert!(skia_color_space_new_from_icc(BorrowedPtr::from_ref(&garbage)).is_null());
+}
+
+#[test]
+fn color_space_get_transfer_fn() {
+    let color_space = ColorSpace::new_srgb_linear();
+    let mut coefficients = ArrayBox::<f32>::new();
+    skia_color_space_get_transfer_fn(
+        BorrowedPtr::from_ref(&color_space),
+        BorrowedPtr::from_mut(&mut coefficients),
+    );
+    assert_eq!(coefficients.to_slice(), [1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+}
+
 #[test]
 fn color_space_drop_accepts_owned_pointer() {
     skia_color_space_drop(OwnedPtr::new(ColorSpace::new_srgb()));
@@ -0,0 +1,91 @@
+use Skia::deferred_broadcast_canvas::{
+    skia_deferred_broadcast_canvas_add_target, skia_deferred_broadcast_canvas_flush,
+    skia_deferred_broadcast_canvas_get_canvas, skia_deferred_broadcast_canvas_new,
+};
+use skia_safe::{Color, Paint, Rect, surfaces};
+use value_box::BorrowedPtr;
+
+#[test]
+fn broadcast_canvas_draws_on_every_target() {
+    let mut first = surfaces::raster_n32_premul((20, 20)).unwrap();
+    let mut second = surfaces::raster_n32_premul((20, 20)).unwrap();
+    first.canvas().clear(Color::WHITE);
+    second.canvas().clear(Color::WHITE);
+
+    let mut broadcast_canvas = skia_deferred_broadcast_canvas_new(20, 20)
+        .with_value_ok(|broadcast_canvas| broadcast_canvas)
+        .unwrap();
+    skia_deferred_broadcast_canvas_add_target(
+        BorrowedPtr::from_mut(&mut broadcast_canvas),
+        BorrowedPtr::from_ref(first.canvas()),
+    );
+    skia_deferred_broadcast_canvas_add_target(
+        BorrowedPtr::from_mut(&mut broadcast_canvas),
+        BorrowedPtr::from_ref(second.canvas()),
+    );
+
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED);
+    skia_deferred_broadcast_canvas_get_canvas(BorrowedPtr::from_mut(&mut broadcast_canvas))
+        .with_ref_ok(|canvas| {
+            canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &paint);
+        })
+        .unwrap();
+    skia_deferred_broadcast_canvas_flush(BorrowedPtr::from_mut(&mut broadcast_canvas));
+    drop(broadcast_canvas);
+
+    for surface in [&mut first, &mut second] {
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::RED);
+        assert_eq!(pixmap.get_color((15, 15)), Color::WHITE);
+    }
+}
+
+#[test]
+fn deferred_broadcast_canvas_flush_in_the_middle_of_a_transform() {
+    let mut target = surfaces::raster_n32_premul((20, 20)).unwrap();
+    target.canvas().clear(Color::WHITE);
+
+    let mut broadcast_canvas = skia_deferred_broadcast_canvas_new(20, 20)
+        .with_value_ok(|broadcast_canvas| broadcast_canvas)
+        .unwrap();
+    skia_deferred_broadcast_canvas_add_target(
+        BorrowedPtr::from_mut(&mut broadcast_canvas),
+        BorrowedPtr::from_ref(target.canvas()),
+    );
+
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED);
+    skia_deferred_broadcast_canvas_get_canvas(BorrowedPtr::from_mut(&mut broadcast_canvas))
+        .with_ref_ok(|canvas| {
+            canvas.save();
+            canvas.translate((10.0, 10.0));
+            canvas.draw_rect(Rect::new(0.0, 0.0, 5.0, 5.0), &paint);
+        })
+        .unwrap();
+    // the target shows nothing until the canvas is flushed
+    assert_eq!(
+        target.peek_pixels().unwrap().get_color((12, 12)),
+        Color::WHITE
+    );
+    skia_deferred_broadcast_canvas_flush(BorrowedPtr::from_mut(&mut broadcast_canvas));
+    assert_eq!(
+        target.peek_pixels().unwrap().get_color((12, 12)),
+        Color::RED
+    );
+    assert_eq!(target.canvas().save_count(), 1);
+
+    // the transform and the save do not span the flush
+    paint.set_color(Color::BLUE);
+    skia_deferred_broadcast_canvas_get_canvas(BorrowedPtr::from_mut(&mut broadcast_canvas))
+        .with_ref_ok(|canvas| {
+            assert!(canvas.total_matrix().is_identity());
+            assert_eq!(canvas.save_count(), 1);
+            canvas.draw_rect(Rect::new(0.0, 0.0, 5.0, 5.0), &paint);
+        })
+        .unwrap();
+    skia_deferred_broadcast_canvas_flush(BorrowedPtr::from_mut(&mut broadcast_canvas));
+    let pixmap = target.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((2, 2)), Color::BLUE);
+    assert_eq!(pixmap.get_color((12, 12)), Color::RED);
+}
@@ -0,0 +1,171 @@
+use Skia::text::font::{
+    skia_font_encoded_text_to_glyphs, skia_font_get_bounds, skia_font_get_path,
+    skia_font_get_paths, skia_font_get_pos, skia_font_get_size, skia_font_get_skew_x,
+    skia_font_get_widths, skia_font_get_x_pos, skia_font_is_embolden, skia_font_new,
+    skia_font_set_embolden, skia_font_set_size, skia_font_unichar_to_glyph, skia_font_with_size,
+};
+use array_box::ArrayBox;
+use skia_safe::{Font, FontMgr, FontStyle, GlyphId, Paint, Path, Point, Rect, TextEncoding};
+use value_box::BorrowedPtr;
+
+fn font() -> Font {
+    let typeface = FontMgr::default()
+        .legacy_make_typeface(None, FontStyle::default())
+        .unwrap();
+    Font::from_typeface(typeface, 20.0)
+}
+
+#[test]
+fn font_new_and_setters() {
+    let typeface = font().typeface();
+    let mut font = skia_font_new(BorrowedPtr::from_ref(&typeface), 12.0, 1.0, -0.25)
+        .with_value_ok(|font| font)
+        .unwrap();
+    assert_eq!(skia_font_get_size(BorrowedPtr::from_ref(&font)), 12.0);
+    assert_eq!(skia_font_get_skew_x(BorrowedPtr::from_ref(&font)), -0.25);
+
+    skia_font_set_size(BorrowedPtr::from_mut(&mut font), 24.0);
+    skia_font_set_embolden(BorrowedPtr::from_mut(&mut font), true);
+    assert_eq!(skia_font_get_size(BorrowedPtr::from_ref(&font)), 24.0);
+    assert!(skia_font_is_embolden(BorrowedPtr::from_ref(&font)));
+
+    let zoomed = skia_font_with_size(BorrowedPtr::from_ref(&font), 48.0)
+        .with_value_ok(|font| font)
+        .unwrap();
+    assert_eq!(zoomed.size(), 48.0);
+    assert!(zoomed.is_embolden());
+    assert_eq!(font.size(), 24.0);
+    assert!(skia_font_with_size(BorrowedPtr::from_ref(&font), -1.0).is_null());
+}
+
+fn encoded_text_to_glyphs(font: &Font, bytes: Vec<u8>, encoding: TextEncoding) -> Vec<GlyphId> {
+    let text = ArrayBox::from_vector(bytes);
+    let mut glyphs = ArrayBox::<GlyphId>::new();
+    let count = skia_font_encoded_text_to_glyphs(
+        BorrowedPtr::from_ref(font),
+        BorrowedPtr::from_ref(&text),
+        encoding,
+        BorrowedPtr::from_mut(&mut glyphs),
+    );
+    assert_eq!(count, glyphs.length);
+    glyphs.to_slice().to_vec()
+}
+
+#[test]
+fn font_encoded_text_to_glyphs() {
+    let font = font();
+    let text = "Hi é";
+    let expected = font.str_to_glyphs_vec(text);
+    assert_eq!(expected.len(), 4);
+
+    let utf8 = text.as_bytes().to_vec();
+    let utf16 = text.encode_utf16().flat_map(u16::to_ne_bytes).collect();
+    let utf32 = text
+        .chars()
+        .flat_map(|c| (c as u32).to_ne_bytes())
+        .collect();
+    let glyph_ids = expected.iter().flat_map(|g| g.to_ne_bytes()).collect();
+    assert_eq!(
+        encoded_text_to_glyphs(&font, utf8, TextEncoding::UTF8),
+        expected
+    );
+    assert_eq!(
+        encoded_text_to_glyphs(&font, utf16, TextEncoding::UTF16),
+        expected
+    );
+    assert_eq!(
+        encoded_text_to_glyphs(&font, utf32, TextEncoding::UTF32),
+        expected
+    );
+    assert_eq!(
+        encoded_text_to_glyphs(&font, glyph_ids, TextEncoding::GlyphId),
+        expected
+    );
+
+    assert!(encoded_text_to_glyphs(&font, vec![0xFF, 0xFE], TextEncoding::UTF8).is_empty());
+    assert!(encoded_text_to_glyphs(&font, vec![0x48], TextEncoding::UTF16).is_empty());
+
+    assert_eq!(
+        skia_font_unichar_to_glyph(BorrowedPtr::from_ref(&font), 'H' as i32),
+        expected[0]
+    );
+}
+
+#[test]
+fn font_glyph_widths_and_positions() {
+    let font = font();
+    let glyphs = ArrayBox::from_vector(font.str_to_glyphs_vec("abc"));
+
+    let mut widths = ArrayBox::<f32>::new();
+    skia_font_get_widths(
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_mut(&mut widths),
+    );
+    let widths = widths.to_slice().to_vec();
+    assert_eq!(widths.len(), 3);
+    assert!(widths.iter().all(|width| *width > 0.0));
+
+    let mut x_positions = ArrayBox::<f32>::new();
+    skia_font_get_x_pos(
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        10.0,
+        BorrowedPtr::from_mut(&mut x_positions),
+    );
+    assert_eq!(
+        x_positions.to_slice(),
+        [10.0, 10.0 + widths[0], 10.0 + widths[0] + widths[1]]
+    );
+
+    let mut positions = ArrayBox::<Point>::new();
+    skia_font_get_pos(
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        10.0,
+        5.0,
+        BorrowedPtr::from_mut(&mut positions),
+    );
+    assert_eq!(positions.to_slice()[1], Point::new(10.0 + widths[0], 5.0));
+
+    let mut bounds = ArrayBox::<Rect>::new();
+    skia_font_get_bounds(
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::<Paint>::null(),
+        BorrowedPtr::from_mut(&mut bounds),
+    );
+    assert_eq!(bounds.length, 3);
+    // glyphs sit on the baseline
+    assert!(bounds.to_slice()[0].top < 0.0);
+}
+
+#[test]
+fn font_glyph_paths() {
+    let font = font();
+    let glyph = font.unichar_to_glyph('o' as i32);
+    let glyph_path = skia_font_get_path(BorrowedPtr::from_ref(&font), glyph)
+        .with_value_ok(|path| path)
+        .unwrap();
+    assert!(!glyph_path.is_empty());
+
+    let glyphs = ArrayBox::from_vector(vec![glyph, glyph]);
+    let positions = ArrayBox::from_vector(vec![Point::new(0.0, 20.0), Point::new(100.0, 20.0)]);
+    let mut path = Path::new();
+    assert!(skia_font_get_paths(
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&positions),
+        BorrowedPtr::from_mut(&mut path),
+    ));
+    assert_eq!(path.count_points(), glyph_path.count_points() * 2);
+    assert!(path.bounds().right > 100.0);
+
+    let positions = ArrayBox::from_vector(vec![Point::new(0.0, 20.0)]);
+    assert!(!skia_font_get_paths(
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&positions),
+        BorrowedPtr::from_mut(&mut path),
+    ));
+}
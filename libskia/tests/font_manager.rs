@@ -0,0 +1,91 @@
+use Skia::text::font_manager::{
+    skia_font_manager_legacy_make_typeface, skia_font_manager_match_family_style_character,
+    skia_font_manager_new_typeface_from_data_with_arguments,
+};
+use Skia::text::font_style::FontStyleWidth;
+use array_box::ArrayBox;
+use skia_safe::font_style::Slant;
+use skia_safe::{FontMgr, Typeface};
+use string_box::StringBox;
+use value_box::{BorrowedPtr, OwnedPtr};
+
+fn legacy_make_typeface(font_mgr: &FontMgr, name: Option<&str>) -> OwnedPtr<Typeface> {
+    let name = name.map(|name| StringBox::from_string(name.to_string()));
+    skia_font_manager_legacy_make_typeface(
+        BorrowedPtr::from_ref(font_mgr),
+        name.as_ref()
+            .map(BorrowedPtr::from_ref)
+            .unwrap_or_else(BorrowedPtr::null),
+        400,
+        FontStyleWidth::Normal,
+        Slant::Upright,
+    )
+}
+
+#[test]
+fn font_manager_legacy_make_typeface() {
+    let font_mgr = FontMgr::default();
+    let typeface = legacy_make_typeface(&font_mgr, None)
+        .with_value_ok(|typeface| typeface)
+        .unwrap();
+    assert!(typeface.count_glyphs() > 0);
+
+    // a missing family falls back to the default typeface
+    assert!(!legacy_make_typeface(&font_mgr, Some("No Such Family")).is_null());
+}
+
+#[test]
+fn font_manager_match_family_style_character() {
+    let font_mgr = FontMgr::default();
+    let name = StringBox::from_string("No Such Family".to_string());
+    let locales = StringBox::from_string("de-CH, en".to_string());
+    let typeface = skia_font_manager_match_family_style_character(
+        BorrowedPtr::from_ref(&font_mgr),
+        BorrowedPtr::from_ref(&name),
+        700,
+        FontStyleWidth::Normal,
+        Slant::Upright,
+        BorrowedPtr::from_ref(&locales),
+        'ß' as i32,
+    )
+    .with_value_ok(|typeface| typeface)
+    .unwrap();
+    assert_ne!(typeface.unichar_to_glyph('ß' as i32), 0);
+}
+
+#[test]
+fn font_manager_new_typeface_from_data_with_arguments() {
+    let font_mgr = FontMgr::default();
+    let typeface = legacy_make_typeface(&font_mgr, None)
+        .with_value_ok(|typeface| typeface)
+        .unwrap();
+    let (bytes, ttc_index) = typeface.to_font_data().unwrap();
+    let data = ArrayBox::from_vector(bytes);
+    let new_typeface = |axis_tags: Vec<u32>, values: Vec<f32>| {
+        skia_font_manager_new_typeface_from_data_with_arguments(
+            BorrowedPtr::from_ref(&font_mgr),
+            BorrowedPtr::from_ref(&data),
+            ttc_index,
+            BorrowedPtr::from_ref(&ArrayBox::from_vector(axis_tags)),
+            BorrowedPtr::from_ref(&ArrayBox::from_vector(values)),
+        )
+    };
+
+    let loaded = new_typeface(vec![], vec![])
+        .with_value_ok(|typeface| typeface)
+        .unwrap();
+    assert_eq!(loaded.family_name(), typeface.family_name());
+    assert!(new_typeface(vec![u32::from_be_bytes(*b"wght")], vec![]).is_null());
+
+    let garbage = ArrayBox::from_vector(vec![1u8, 2, 3]);
+    assert!(
+        skia_font_manager_new_typeface_from_data_with_arguments(
+            BorrowedPtr::from_ref(&font_mgr),
+            BorrowedPtr::from_ref(&garbage),
+            0,
+            BorrowedPtr::from_ref(&ArrayBox::<u32>::new()),
+            BorrowedPtr::from_ref(&ArrayBox::<f32>::new()),
+        )
+        .is_null()
+    );
+}
@@ -0,0 +1,271 @@
+use Skia::image::{
+    skia_image_from_picture, skia_image_make_color_space,
+    skia_image_make_color_type_and_color_space, skia_image_make_non_texture_image,
+    skia_image_make_raster_image, skia_image_make_subset, skia_image_make_with_filter,
+    skia_image_read_pixels, skia_image_reinterpret_color_space, skia_image_scale_pixels,
+};
+use array_box::ArrayBox;
+use skia_safe::image::CachingHint;
+use skia_safe::images::BitDepth;
+use skia_safe::{
+    AlphaType, Color, ColorSpace, ColorType, IPoint, IRect, ISize, Image, ImageInfo, Paint,
+    PictureRecorder, Rect, SamplingOptions, image_filters, surfaces,
+};
+use value_box::BorrowedPtr;
+
+/// A 10x10 image that is red on the left and blue on the right
+fn red_and_blue() -> Image {
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    surface.canvas().clear(Color::RED);
+    let mut paint = Paint::default();
+    paint.set_color(Color::BLUE);
+    surface
+        .canvas()
+        .draw_rect(Rect::new(5.0, 0.0, 10.0, 10.0), &paint);
+    surface.image_snapshot()
+}
+
+/// A 4x4 sRGB image filled with a mid gray
+fn gray() -> Image {
+    let image_info = ImageInfo::new_n32_premul((4, 4), Some(ColorSpace::new_srgb()));
+    let mut surface = surfaces::raster(&image_info, None, None).unwrap();
+    surface.canvas().clear(Color::from_rgb(0x80, 0x80, 0x80));
+    surface.image_snapshot()
+}
+
+fn color_at(image: &Image, x: i32, y: i32) -> Color {
+    image.peek_pixels().unwrap().get_color((x, y))
+}
+
+/// sRGB 0x80 is 0x37 in linear sRGB
+fn assert_linear_gray(color: Color) {
+    assert!((0x36..=0x38).contains(&color.r()), "{:?}", color);
+    assert_eq!(color.r(), color.g());
+    assert_eq!(color.r(), color.b());
+}
+
+#[test]
+fn image_make_subset() {
+    let image = red_and_blue();
+    skia_image_make_subset(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::null(),
+        BorrowedPtr::from_ref(&IRect::new(4, 2, 8, 10)),
+    )
+    .with_value_ok(|subset| {
+        assert_eq!(subset.width(), 4);
+        assert_eq!(subset.height(), 8);
+        assert_eq!(color_at(&subset, 0, 0), Color::RED);
+        assert_eq!(color_at(&subset, 1, 7), Color::BLUE);
+    })
+    .unwrap();
+
+    // the subset must be within the image
+    assert!(
+        skia_image_make_subset(
+            BorrowedPtr::from_ref(&image),
+            BorrowedPtr::null(),
+            BorrowedPtr::from_ref(&IRect::new(5, 5, 15, 15)),
+        )
+        .is_null()
+    );
+}
+
+#[test]
+fn image_make_color_space_converts_the_pixels() {
+    let image = gray();
+    let linear = ColorSpace::new_srgb_linear();
+    skia_image_make_color_space(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::null(),
+        BorrowedPtr::from_ref(&linear),
+    )
+    .with_value_ok(|converted| {
+        assert!(converted.color_space().unwrap().gamma_is_linear());
+        assert_linear_gray(color_at(&converted, 1, 1));
+    })
+    .unwrap();
+}
+
+#[test]
+fn image_reinterpret_color_space_keeps_the_pixels() {
+    let image = gray();
+    skia_image_reinterpret_color_space(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::from_ref(&ColorSpace::new_srgb_linear()),
+    )
+    .with_value_ok(|reinterpreted| {
+        assert!(reinterpreted.color_space().unwrap().gamma_is_linear());
+        assert_eq!(color_at(&reinterpreted, 1, 1), color_at(&image, 1, 1));
+    })
+    .unwrap();
+}
+
+#[test]
+fn image_make_color_type_and_color_space() {
+    let image = gray();
+    skia_image_make_color_type_and_color_space(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::null(),
+        ColorType::RGBA8888,
+        BorrowedPtr::from_ref(&ColorSpace::new_srgb_linear()),
+    )
+    .with_value_ok(|converted| {
+        assert_eq!(converted.color_type(), ColorType::RGBA8888);
+        assert_eq!(converted.alpha_type(), image.alpha_type());
+        assert_eq!(converted.dimensions(), image.dimensions());
+        assert_linear_gray(color_at(&converted, 3, 3));
+    })
+    .unwrap();
+
+    skia_image_make_color_type_and_color_space(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::null(),
+        ColorType::Gray8,
+        BorrowedPtr::null(),
+    )
+    .with_value_ok(|converted| {
+        assert_eq!(converted.color_type(), ColorType::Gray8);
+        assert!(converted.color_space().is_none());
+    })
+    .unwrap();
+}
+
+#[test]
+fn image_make_raster_image_decodes_lazy_images() {
+    let mut recorder = PictureRecorder::new();
+    recorder
+        .begin_recording(Rect::new(0.0, 0.0, 10.0, 10.0), false)
+        .clear(Color::GREEN);
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let lazy = skia_image_from_picture(
+        BorrowedPtr::from_ref(&picture),
+        10,
+        10,
+        BorrowedPtr::null(),
+        BorrowedPtr::null(),
+        BitDepth::U8,
+        BorrowedPtr::null(),
+    )
+    .with_value_ok(|image| image)
+    .unwrap();
+    assert!(lazy.is_lazy_generated());
+
+    skia_image_make_raster_image(
+        BorrowedPtr::from_ref(&lazy),
+        BorrowedPtr::null(),
+        CachingHint::Disallow,
+    )
+    .with_value_ok(|raster| {
+        assert!(!raster.is_lazy_generated());
+        assert_eq!(color_at(&raster, 5, 5), Color::GREEN);
+    })
+    .unwrap();
+}
+
+#[test]
+fn image_make_non_texture_image_keeps_raster_images() {
+    let image = red_and_blue();
+    skia_image_make_non_texture_image(BorrowedPtr::from_ref(&image), BorrowedPtr::null())
+        .with_value_ok(|non_texture| {
+            assert!(!non_texture.is_texture_backed());
+            assert_eq!(non_texture.unique_id(), image.unique_id());
+        })
+        .unwrap();
+}
+
+#[test]
+fn image_make_with_filter() {
+    let image = red_and_blue();
+    let image_filter = image_filters::offset((3.0, 4.0), None, None).unwrap();
+    let mut out_subset = IRect::new_empty();
+    let mut offset = IPoint::new(0, 0);
+
+    skia_image_make_with_filter(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::from_ref(&image_filter),
+        BorrowedPtr::from_ref(&IRect::new(0, 0, 10, 10)),
+        BorrowedPtr::from_ref(&IRect::new(0, 0, 20, 20)),
+        BorrowedPtr::from_mut(&mut out_subset),
+        BorrowedPtr::from_mut(&mut offset),
+    )
+    .with_value_ok(|filtered| {
+        assert_eq!(offset, IPoint::new(3, 4));
+        assert_eq!(out_subset.size(), ISize::new(10, 10));
+        assert!(filtered.width() >= out_subset.right());
+        assert_eq!(
+            color_at(&filtered, out_subset.left(), out_subset.top()),
+            Color::RED
+        );
+    })
+    .unwrap();
+}
+
+#[test]
+fn image_scale_pixels() {
+    let image = red_and_blue();
+    let image_info = ImageInfo::new_n32_premul((20, 30), None);
+    skia_image_scale_pixels(
+        BorrowedPtr::from_ref(&image),
+        BorrowedPtr::from_ref(&image_info),
+        BorrowedPtr::from_ref(&SamplingOptions::default()),
+    )
+    .with_value_ok(|scaled| {
+        assert_eq!(scaled.width(), 20);
+        assert_eq!(scaled.height(), 30);
+        assert_eq!(color_at(&scaled, 2, 25), Color::RED);
+        assert_eq!(color_at(&scaled, 17, 25), Color::BLUE);
+    })
+    .unwrap();
+
+    // the pixels can not be scaled into an unknown color type
+    let image_info = ImageInfo::new((20, 30), ColorType::Unknown, image.alpha_type(), None);
+    assert!(
+        skia_image_scale_pixels(
+            BorrowedPtr::from_ref(&image),
+            BorrowedPtr::from_ref(&image_info),
+            BorrowedPtr::from_ref(&SamplingOptions::default()),
+        )
+        .is_null()
+    );
+
+    // pixels that do not fit in memory are not allocated
+    let image_info = ImageInfo::new_n32_premul((i32::MAX, i32::MAX), None);
+    assert!(
+        skia_image_scale_pixels(
+            BorrowedPtr::from_ref(&image),
+            BorrowedPtr::from_ref(&image_info),
+            BorrowedPtr::from_ref(&SamplingOptions::default()),
+        )
+        .is_null()
+    );
+}
+
+#[test]
+fn image_read_pixels() {
+    let image = red_and_blue();
+    let image_info = ImageInfo::new((4, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+    let mut pixels = ArrayBox::from_vector(vec![0u8; 4 * 2 * 4]);
+    let mut read_pixels = |x: i32, y: i32| {
+        skia_image_read_pixels(
+            BorrowedPtr::from_ref(&image),
+            x,
+            y,
+            BorrowedPtr::from_ref(&image_info),
+            BorrowedPtr::from_mut(&mut pixels),
+            16,
+        )
+    };
+
+    assert!(read_pixels(3, 6));
+    assert!(!read_pixels(10, 0));
+    assert!(!read_pixels(0, -2));
+
+    // the columns 3 and 4 are red, 5 and 6 are blue
+    let pixels = pixels.to_slice();
+    assert_eq!(pixels[0..4], [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(pixels[4..8], [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(pixels[8..12], [0x00, 0x00, 0xFF, 0xFF]);
+    assert_eq!(pixels[28..32], [0x00, 0x00, 0xFF, 0xFF]);
+}
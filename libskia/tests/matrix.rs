@@ -0,0 +1,151 @@
+use Skia::m44::{
+    skia_m44_get_all, skia_m44_invert, skia_m44_map, skia_m44_new_perspective, skia_m44_new_rotate,
+    skia_m44_new_translate,
+};
+use Skia::matrix::{
+    skia_matrix_has_perspective, skia_matrix_invert, skia_matrix_is_scale_translate,
+    skia_matrix_map_points, skia_matrix_map_radius, skia_matrix_new_rect_to_rect,
+    skia_matrix_new_rotate, skia_matrix_new_scale, skia_matrix_new_translate,
+    skia_matrix_post_concat,
+};
+use array_box::ArrayBox;
+use skia_safe::matrix::ScaleToFit;
+use skia_safe::{M44, Matrix, Point, Rect};
+use value_box::BorrowedPtr;
+
+#[test]
+fn matrix_invert() {
+    let matrix = skia_matrix_new_scale(2.0, 4.0)
+        .with_value_ok(|matrix| matrix)
+        .unwrap();
+    let mut inverse = Matrix::new_identity();
+    assert!(skia_matrix_invert(
+        BorrowedPtr::from_ref(&matrix),
+        BorrowedPtr::from_mut(&mut inverse)
+    ));
+    assert_eq!(inverse, Matrix::scale((0.5, 0.25)));
+
+    let singular = Matrix::scale((0.0, 1.0));
+    assert!(!skia_matrix_invert(
+        BorrowedPtr::from_ref(&singular),
+        BorrowedPtr::from_mut(&mut inverse)
+    ));
+    assert_eq!(inverse, Matrix::scale((0.5, 0.25)));
+}
+
+#[test]
+fn matrix_post_concat_and_map_points() {
+    let mut matrix = skia_matrix_new_scale(2.0, 2.0)
+        .with_value_ok(|matrix| matrix)
+        .unwrap();
+    let translate = skia_matrix_new_translate(10.0, 20.0)
+        .with_value_ok(|matrix| matrix)
+        .unwrap();
+    skia_matrix_post_concat(
+        BorrowedPtr::from_mut(&mut matrix),
+        BorrowedPtr::from_ref(&translate),
+    );
+    assert!(skia_matrix_is_scale_translate(BorrowedPtr::from_ref(
+        &matrix
+    )));
+
+    let mut points = ArrayBox::from_vector(vec![Point::new(1.0, 1.0), Point::new(-1.0, 0.0)]);
+    skia_matrix_map_points(
+        BorrowedPtr::from_ref(&matrix),
+        BorrowedPtr::from_mut(&mut points),
+    );
+    assert_eq!(
+        points.to_slice(),
+        &[Point::new(12.0, 22.0), Point::new(8.0, 20.0)]
+    );
+    assert_eq!(
+        skia_matrix_map_radius(BorrowedPtr::from_ref(&matrix), 3.0),
+        6.0
+    );
+}
+
+#[test]
+fn matrix_rotate_around_pivot() {
+    skia_matrix_new_rotate(90.0, 10.0, 10.0)
+        .with_value_ok(|matrix| {
+            let point = matrix.map_xy(20.0, 10.0);
+            assert!((point.x - 10.0).abs() < 1e-4);
+            assert!((point.y - 20.0).abs() < 1e-4);
+            assert!(!skia_matrix_has_perspective(BorrowedPtr::from_ref(&matrix)));
+        })
+        .unwrap();
+}
+
+#[test]
+fn matrix_rect_to_rect_center() {
+    let source = Rect::from_wh(10.0, 10.0);
+    let destination = Rect::from_xywh(0.0, 0.0, 40.0, 20.0);
+    skia_matrix_new_rect_to_rect(
+        BorrowedPtr::from_ref(&source),
+        BorrowedPtr::from_ref(&destination),
+        ScaleToFit::Center,
+    )
+    .with_value_ok(|matrix| {
+        assert_eq!(
+            matrix.map_rect(source).0,
+            Rect::from_xywh(10.0, 0.0, 20.0, 20.0)
+        );
+    })
+    .unwrap();
+
+    let empty = Rect::new_empty();
+    assert!(
+        skia_matrix_new_rect_to_rect(
+            BorrowedPtr::from_ref(&empty),
+            BorrowedPtr::from_ref(&destination),
+            ScaleToFit::Fill,
+        )
+        .is_null()
+    );
+}
+
+#[test]
+fn m44_translate_and_invert() {
+    let m44 = skia_m44_new_translate(1.0, 2.0, 3.0)
+        .with_value_ok(|m44| m44)
+        .unwrap();
+    let mut members = ArrayBox::<f32>::new();
+    skia_m44_get_all(
+        BorrowedPtr::from_ref(&m44),
+        BorrowedPtr::from_mut(&mut members),
+    );
+    assert_eq!(members.to_slice()[3], 1.0);
+    assert_eq!(members.to_slice()[7], 2.0);
+    assert_eq!(members.to_slice()[11], 3.0);
+
+    let mut inverse = M44::new_identity();
+    assert!(skia_m44_invert(
+        BorrowedPtr::from_ref(&m44),
+        BorrowedPtr::from_mut(&mut inverse)
+    ));
+    let mut mapped = ArrayBox::<f32>::new();
+    skia_m44_map(
+        BorrowedPtr::from_ref(&inverse),
+        1.0,
+        2.0,
+        3.0,
+        1.0,
+        BorrowedPtr::from_mut(&mut mapped),
+    );
+    assert_eq!(mapped.to_slice(), &[0.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn m44_rotate_and_perspective() {
+    skia_m44_new_rotate(0.0, 0.0, 1.0, std::f32::consts::FRAC_PI_2)
+        .with_value_ok(|m44| {
+            let mapped = m44.map(1.0, 0.0, 0.0, 1.0);
+            assert!(mapped.x.abs() < 1e-6);
+            assert!((mapped.y - 1.0).abs() < 1e-6);
+        })
+        .unwrap();
+
+    skia_m44_new_perspective(1.0, 100.0, std::f32::consts::FRAC_PI_2)
+        .with_value_ok(|m44| assert!(m44.invert().is_some()))
+        .unwrap();
+}
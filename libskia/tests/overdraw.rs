@@ -0,0 +1,137 @@
+use Skia::overdraw::{
+    OverdrawCanvas, skia_overdraw_canvas_draw_picture, skia_overdraw_canvas_flush,
+    skia_overdraw_canvas_get_canvas, skia_overdraw_canvas_get_counts,
+    skia_overdraw_canvas_is_supported, skia_overdraw_canvas_make_heatmap, skia_overdraw_canvas_new,
+};
+use array_box::ArrayBox;
+use skia_safe::{
+    BlendMode, Color, ImageInfo, Paint, Picture, PictureRecorder, Rect, Surface, surfaces,
+};
+use value_box::BorrowedPtr;
+
+fn target() -> Surface {
+    let mut surface = surfaces::raster(&ImageInfo::new_a8((20, 20)), None, None).unwrap();
+    surface.canvas().clear(Color::TRANSPARENT);
+    surface
+}
+
+fn overlapping_rects() -> Picture {
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 20.0, 20.0), false);
+    // the color and the blend mode of the paints do not matter
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_argb(0x40, 0xFF, 0, 0));
+    canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &paint);
+    paint.set_blend_mode(BlendMode::Src);
+    paint.set_color(Color::WHITE);
+    canvas.draw_rect(Rect::new(5.0, 5.0, 15.0, 15.0), &paint);
+    recorder.finish_recording_as_picture(None).unwrap()
+}
+
+fn counts(overdraw_canvas: BorrowedPtr<OverdrawCanvas>) -> Vec<u8> {
+    let mut counts = ArrayBox::<u8>::new();
+    assert!(skia_overdraw_canvas_get_counts(
+        overdraw_canvas,
+        BorrowedPtr::from_mut(&mut counts),
+    ));
+    counts.to_slice().to_vec()
+}
+
+#[test]
+fn overdraw_canvas_is_supported_by_the_linked_skia() {
+    assert!(skia_overdraw_canvas_is_supported());
+}
+
+#[test]
+fn overdraw_canvas_counts_overlapping_rects() {
+    let mut target = target();
+    let mut overdraw_canvas = skia_overdraw_canvas_new(BorrowedPtr::from_ref(target.canvas()))
+        .with_value_ok(|overdraw_canvas| overdraw_canvas)
+        .unwrap();
+
+    let picture = overlapping_rects();
+    skia_overdraw_canvas_get_canvas(BorrowedPtr::from_mut(&mut overdraw_canvas))
+        .with_ref_ok(|canvas| canvas.draw_picture(&picture, None, None))
+        .unwrap();
+    assert!(skia_overdraw_canvas_flush(BorrowedPtr::from_mut(
+        &mut overdraw_canvas
+    )));
+
+    let counts = counts(BorrowedPtr::from_mut(&mut overdraw_canvas));
+    assert_eq!(counts.len(), 400);
+    assert_eq!(counts[2 * 20 + 2], 1);
+    assert_eq!(counts[7 * 20 + 7], 2);
+    assert_eq!(counts[12 * 20 + 12], 1);
+    assert_eq!(counts[17 * 20 + 17], 0);
+
+    // the counts add up over several pictures
+    assert!(skia_overdraw_canvas_draw_picture(
+        BorrowedPtr::from_ref(&overdraw_canvas),
+        BorrowedPtr::from_ref(&picture),
+    ));
+    let counts = counts(BorrowedPtr::from_mut(&mut overdraw_canvas));
+    assert_eq!(counts[2 * 20 + 2], 2);
+    assert_eq!(counts[7 * 20 + 7], 4);
+    assert_eq!(counts[17 * 20 + 17], 0);
+}
+
+#[test]
+fn overdraw_canvas_counts_images_and_layers() {
+    let image = {
+        let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+        surface.canvas().clear(Color::BLUE);
+        surface.image_snapshot()
+    };
+
+    let mut target = target();
+    let mut overdraw_canvas = skia_overdraw_canvas_new(BorrowedPtr::from_ref(target.canvas()))
+        .with_value_ok(|overdraw_canvas| overdraw_canvas)
+        .unwrap();
+    skia_overdraw_canvas_get_canvas(BorrowedPtr::from_mut(&mut overdraw_canvas))
+        .with_ref_ok(|canvas| {
+            // an image without a paint, and a rect in a translucent layer
+            canvas.draw_image(&image, (0, 0), None);
+            canvas.save_layer_alpha_f(None, 0.5);
+            canvas.draw_rect(Rect::new(5.0, 5.0, 15.0, 15.0), &Paint::default());
+            canvas.restore();
+        })
+        .unwrap();
+
+    let counts = counts(BorrowedPtr::from_mut(&mut overdraw_canvas));
+    assert_eq!(counts[2 * 20 + 2], 1);
+    assert_eq!(counts[7 * 20 + 7], 2);
+    assert_eq!(counts[12 * 20 + 12], 1);
+}
+
+#[test]
+fn overdraw_canvas_heatmap() {
+    let mut target = target();
+    let mut overdraw_canvas = skia_overdraw_canvas_new(BorrowedPtr::from_ref(target.canvas()))
+        .with_value_ok(|overdraw_canvas| overdraw_canvas)
+        .unwrap();
+    assert!(skia_overdraw_canvas_draw_picture(
+        BorrowedPtr::from_ref(&overdraw_canvas),
+        BorrowedPtr::from_ref(&overlapping_rects()),
+    ));
+
+    skia_overdraw_canvas_make_heatmap(
+        BorrowedPtr::from_mut(&mut overdraw_canvas),
+        BorrowedPtr::null(),
+    )
+    .with_value_ok(|heatmap| {
+        assert_eq!(heatmap.width(), 20);
+        let pixmap = heatmap.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((17, 17)).a(), 0);
+        assert_eq!(pixmap.get_color((7, 7)).a(), 0x80);
+    })
+    .unwrap();
+
+    let colors = ArrayBox::from_vector(vec![Color::RED; 5]);
+    assert!(
+        skia_overdraw_canvas_make_heatmap(
+            BorrowedPtr::from_mut(&mut overdraw_canvas),
+            BorrowedPtr::from_ref(&colors),
+        )
+        .is_null()
+    );
+}
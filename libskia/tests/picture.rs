@@ -0,0 +1,130 @@
+use Skia::image::skia_image_from_picture;
+use Skia::picture::{
+    skia_picture_approximate_bytes_used, skia_picture_deserialize, skia_picture_make_from_drawable,
+    skia_picture_playback_with_matrix_and_clip, skia_picture_serialize,
+    skia_picture_to_raster_image,
+};
+use Skia::recorder::picture_recorder::{
+    skia_picture_recorder_begin_recording_with_rtree,
+    skia_picture_recorder_finish_recording_as_drawable,
+};
+use array_box::ArrayBox;
+use skia_safe::images::BitDepth;
+use skia_safe::{Color, Matrix, Paint, Picture, PictureRecorder, Rect, surfaces};
+use value_box::BorrowedPtr;
+
+fn record_red_square() -> Picture {
+    let mut recorder = PictureRecorder::new();
+    let canvas = skia_picture_recorder_begin_recording_with_rtree(
+        BorrowedPtr::from_mut(&mut recorder),
+        0.0,
+        0.0,
+        10.0,
+        10.0,
+    );
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED);
+    canvas
+        .with_ref_ok(|canvas| {
+            canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &paint);
+        })
+        .unwrap();
+    recorder.finish_recording_as_picture(None).unwrap()
+}
+
+#[test]
+fn picture_serialize_and_deserialize() {
+    let picture = record_red_square();
+    assert!(skia_picture_approximate_bytes_used(BorrowedPtr::from_ref(&picture)) > 0);
+
+    let mut data = ArrayBox::<u8>::new();
+    skia_picture_serialize(
+        BorrowedPtr::from_ref(&picture),
+        BorrowedPtr::from_mut(&mut data),
+    );
+
+    skia_picture_deserialize(BorrowedPtr::from_ref(&data))
+        .with_value_ok(|deserialized| {
+            assert_eq!(deserialized.cull_rect(), picture.cull_rect());
+        })
+        .unwrap();
+
+    let garbage = ArrayBox::from_vector(vec![0u8; 16]);
+    assert!(skia_picture_deserialize(BorrowedPtr::from_ref(&garbage)).is_null());
+}
+
+#[test]
+fn picture_playback_with_matrix_and_clip() {
+    let picture = record_red_square();
+    let mut surface = surfaces::raster_n32_premul((40, 40)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+
+    let matrix = Matrix::scale((4.0, 4.0));
+    let clip = Rect::new(0.0, 0.0, 20.0, 40.0);
+    skia_picture_playback_with_matrix_and_clip(
+        BorrowedPtr::from_ref(&picture),
+        BorrowedPtr::from_ref(surface.canvas()),
+        BorrowedPtr::from_ref(&matrix),
+        BorrowedPtr::from_ref(&clip),
+    );
+    assert_eq!(surface.canvas().save_count(), 1);
+
+    let pixmap = surface.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((10, 30)), Color::RED);
+    assert_eq!(pixmap.get_color((30, 10)), Color::WHITE);
+}
+
+#[test]
+fn picture_from_drawable() {
+    let mut recorder = PictureRecorder::new();
+    recorder.begin_recording(Rect::new(0.0, 0.0, 5.0, 5.0), false);
+    let mut drawable =
+        skia_picture_recorder_finish_recording_as_drawable(BorrowedPtr::from_mut(&mut recorder))
+            .with_value_ok(|drawable| drawable)
+            .unwrap();
+
+    assert!(!skia_picture_make_from_drawable(BorrowedPtr::from_mut(&mut drawable)).is_null());
+}
+
+#[test]
+fn picture_to_raster_image() {
+    let picture = record_red_square();
+    skia_picture_to_raster_image(BorrowedPtr::from_ref(&picture), 2.0, 255, 255, 255, 255)
+        .with_value_ok(|image| {
+            assert_eq!(image.width(), 20);
+            assert_eq!(image.height(), 20);
+            let pixmap = image.peek_pixels().unwrap();
+            assert_eq!(pixmap.get_color((19, 19)), Color::RED);
+        })
+        .unwrap();
+
+    let empty = Picture::new_placeholder(Rect::new_empty());
+    assert!(skia_picture_to_raster_image(BorrowedPtr::from_ref(&empty), 1.0, 0, 0, 0, 0).is_null());
+}
+
+#[test]
+fn image_from_picture() {
+    let picture = record_red_square();
+    let matrix = Matrix::translate((5.0, 5.0));
+    skia_image_from_picture(
+        BorrowedPtr::from_ref(&picture),
+        20,
+        20,
+        BorrowedPtr::from_ref(&matrix),
+        BorrowedPtr::null(),
+        BitDepth::U8,
+        BorrowedPtr::null(),
+    )
+    .with_value_ok(|image| {
+        assert_eq!(image.width(), 20);
+        assert_eq!(image.height(), 20);
+
+        let mut surface = surfaces::raster_n32_premul((20, 20)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        surface.canvas().draw_image(&image, (0, 0), None);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((2, 2)), Color::WHITE);
+        assert_eq!(pixmap.get_color((10, 10)), Color::RED);
+    })
+    .unwrap();
+}
@@ -0,0 +1,123 @@
+use Skia::picture_debugger::{
+    skia_picture_debugger_command_count, skia_picture_debugger_commands_to_json,
+    skia_picture_debugger_is_supported, skia_picture_debugger_playback_to,
+};
+use skia_safe::{
+    Color, Font, FontMgr, FontStyle, Paint, Picture, PictureRecorder, Rect, TextBlob, surfaces,
+};
+use string_box::StringBox;
+use value_box::BorrowedPtr;
+
+fn record_translated_red_square() -> Picture {
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 40.0, 40.0), false);
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED);
+    canvas.save();
+    canvas.translate((10.0, 10.0));
+    canvas.draw_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &paint);
+    canvas.restore();
+    recorder.finish_recording_as_picture(None).unwrap()
+}
+
+fn commands_json(picture: &Picture) -> String {
+    let mut json = StringBox::new();
+    skia_picture_debugger_commands_to_json(
+        BorrowedPtr::from_ref(picture),
+        BorrowedPtr::from_mut(&mut json),
+    );
+    json.to_string()
+}
+
+#[test]
+fn picture_debugger_is_supported_by_the_linked_skia() {
+    assert!(skia_picture_debugger_is_supported());
+}
+
+#[test]
+fn picture_debugger_commands_to_json() {
+    let picture = record_translated_red_square();
+    let json = commands_json(&picture);
+
+    assert!(json.starts_with("{\"cullRect\":[0,0,40,40],\"commands\":["));
+    assert!(json.contains("\"command\":\"Save\""));
+    assert!(json.contains("\"command\":\"Translate\""));
+    assert!(json.contains(
+        "\"command\":\"DrawRect\",\"bounds\":[10,10,20,20],\"matrix\":[1,0,10,0,1,10,0,0,1]"
+    ));
+    assert!(json.contains("\"color\":[1,0,0,1],\"style\":\"Fill\""));
+    assert!(json.contains("\"command\":\"Restore\""));
+
+    let count = skia_picture_debugger_command_count(BorrowedPtr::from_ref(&picture));
+    assert_eq!(json.matches("\"index\":").count() as isize, count);
+}
+
+#[test]
+fn picture_debugger_reads_the_tables_after_the_typefaces() {
+    let typeface = FontMgr::default()
+        .legacy_make_typeface(None, FontStyle::default())
+        .unwrap();
+    let text_blob = TextBlob::from_str("text", &Font::from_typeface(typeface, 20.0)).unwrap();
+    let image = {
+        let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+        surface.canvas().clear(Color::BLUE);
+        surface.image_snapshot()
+    };
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 100.0, 100.0), false);
+    canvas.draw_text_blob(&text_blob, (10.0, 50.0), &Paint::default());
+    canvas.draw_image(&image, (60.0, 60.0), None);
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let json = commands_json(&picture);
+    // the bounds of the text blob come from the tables that follow the typefaces
+    assert!(json.contains("\"command\":\"DrawTextBlob\",\"bounds\":["));
+    assert!(json.contains("\"command\":\"DrawImage\""));
+    assert!(!json.contains("Retired"));
+}
+
+#[test]
+fn picture_debugger_playback_to() {
+    let picture = record_translated_red_square();
+    let json = commands_json(&picture);
+    let draw_rect = json[..json.find("\"command\":\"DrawRect\"").unwrap()]
+        .matches("\"index\":")
+        .count()
+        - 1;
+
+    let mut surface = surfaces::raster_n32_premul((40, 40)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+    assert!(skia_picture_debugger_playback_to(
+        BorrowedPtr::from_ref(&picture),
+        BorrowedPtr::from_ref(surface.canvas()),
+        draw_rect,
+    ));
+    assert_eq!(
+        surface.peek_pixels().unwrap().get_color((15, 15)),
+        Color::WHITE
+    );
+
+    assert!(skia_picture_debugger_playback_to(
+        BorrowedPtr::from_ref(&picture),
+        BorrowedPtr::from_ref(surface.canvas()),
+        draw_rect + 1,
+    ));
+    assert_eq!(
+        surface.peek_pixels().unwrap().get_color((15, 15)),
+        Color::RED
+    );
+    assert_eq!(surface.canvas().save_count(), 1);
+
+    // a count past the last command plays back the whole picture
+    surface.canvas().clear(Color::WHITE);
+    assert!(skia_picture_debugger_playback_to(
+        BorrowedPtr::from_ref(&picture),
+        BorrowedPtr::from_ref(surface.canvas()),
+        usize::MAX,
+    ));
+    assert_eq!(
+        surface.peek_pixels().unwrap().get_color((15, 15)),
+        Color::RED
+    );
+}
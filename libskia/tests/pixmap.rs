@@ -0,0 +1,54 @@
+use Skia::image::skia_image_peek_pixels;
+use Skia::pixmap::{
+    skia_pixmap_erase, skia_pixmap_get_color, skia_pixmap_get_height, skia_pixmap_get_width,
+};
+use Skia::surface::skia_surface_peek_pixels;
+use skia_safe::{Color, IRect, surfaces};
+use value_box::BorrowedPtr;
+
+#[test]
+fn surface_peek_pixels_shares_the_pixels() {
+    let mut surface = surfaces::raster_n32_premul((8, 8)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+
+    // the pixmap outlives the borrow of the surface it was peeked from
+    let mut pixmap = skia_surface_peek_pixels(BorrowedPtr::from_mut(&mut surface))
+        .with_value_ok(|pixmap| pixmap)
+        .unwrap();
+    assert_eq!(skia_pixmap_get_width(BorrowedPtr::from_ref(&pixmap)), 8);
+    assert_eq!(skia_pixmap_get_height(BorrowedPtr::from_ref(&pixmap)), 8);
+
+    // what is drawn on the surface is visible through the pixmap
+    surface.canvas().clear(Color::RED);
+    skia_pixmap_get_color(BorrowedPtr::from_ref(&pixmap), 7, 7)
+        .with_value_ok(|color| assert_eq!(color, Color::RED))
+        .unwrap();
+    assert!(skia_pixmap_get_color(BorrowedPtr::from_ref(&pixmap), 8, 0).is_null());
+
+    // and what is written to the pixmap is visible on the surface
+    assert!(skia_pixmap_erase(
+        BorrowedPtr::from_mut(&mut pixmap),
+        BorrowedPtr::from_ref(&Color::BLUE),
+        BorrowedPtr::from_ref(&IRect::new(0, 0, 4, 4)),
+    ));
+    drop(pixmap);
+
+    let pixels = surface.peek_pixels().unwrap();
+    assert_eq!(pixels.get_color((1, 1)), Color::BLUE);
+    assert_eq!(pixels.get_color((6, 6)), Color::RED);
+}
+
+#[test]
+fn image_peek_pixels() {
+    let mut surface = surfaces::raster_n32_premul((8, 8)).unwrap();
+    surface.canvas().clear(Color::GREEN);
+    let image = surface.image_snapshot();
+    drop(surface);
+
+    let pixmap = skia_image_peek_pixels(BorrowedPtr::from_ref(&image))
+        .with_value_ok(|pixmap| pixmap)
+        .unwrap();
+    skia_pixmap_get_color(BorrowedPtr::from_ref(&pixmap), 3, 3)
+        .with_value_ok(|color| assert_eq!(color, Color::GREEN))
+        .unwrap();
+}
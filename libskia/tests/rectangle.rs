@@ -0,0 +1,67 @@
+use Skia::rectangle::{
+    skia_rectangle_f32_contains_point, skia_rectangle_f32_intersect, skia_rectangle_f32_join,
+    skia_rectangle_f32_round_out, skia_rectangle_f32_sort, skia_rectangle_i32_intersect,
+};
+use skia_safe::{IRect, Rect};
+use value_box::BorrowedPtr;
+
+#[test]
+fn rectangle_f32_intersect_and_join() {
+    let mut rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+    let other = Rect::new(5.0, 5.0, 20.0, 20.0);
+    assert!(skia_rectangle_f32_intersect(
+        BorrowedPtr::from_mut(&mut rect),
+        BorrowedPtr::from_ref(&other)
+    ));
+    assert_eq!(rect, Rect::new(5.0, 5.0, 10.0, 10.0));
+
+    let disjoint = Rect::new(50.0, 50.0, 60.0, 60.0);
+    assert!(!skia_rectangle_f32_intersect(
+        BorrowedPtr::from_mut(&mut rect),
+        BorrowedPtr::from_ref(&disjoint)
+    ));
+    assert_eq!(rect, Rect::new(5.0, 5.0, 10.0, 10.0));
+
+    skia_rectangle_f32_join(
+        BorrowedPtr::from_mut(&mut rect),
+        BorrowedPtr::from_ref(&disjoint),
+    );
+    assert_eq!(rect, Rect::new(5.0, 5.0, 60.0, 60.0));
+}
+
+#[test]
+fn rectangle_f32_contains_point_excludes_right_and_bottom_edges() {
+    let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+    assert!(skia_rectangle_f32_contains_point(
+        BorrowedPtr::from_ref(&rect),
+        0.0,
+        0.0
+    ));
+    assert!(!skia_rectangle_f32_contains_point(
+        BorrowedPtr::from_ref(&rect),
+        10.0,
+        5.0
+    ));
+}
+
+#[test]
+fn rectangle_f32_sort_and_round_out() {
+    let mut rect = Rect::new(10.5, 10.5, 0.5, 0.5);
+    skia_rectangle_f32_sort(BorrowedPtr::from_mut(&mut rect));
+    assert_eq!(rect, Rect::new(0.5, 0.5, 10.5, 10.5));
+
+    skia_rectangle_f32_round_out(BorrowedPtr::from_ref(&rect))
+        .with_value_ok(|rounded| assert_eq!(rounded, IRect::new(0, 0, 11, 11)))
+        .unwrap();
+}
+
+#[test]
+fn rectangle_i32_intersect() {
+    let mut rect = IRect::new(0, 0, 10, 10);
+    let other = IRect::new(5, -5, 15, 5);
+    assert!(skia_rectangle_i32_intersect(
+        BorrowedPtr::from_mut(&mut rect),
+        BorrowedPtr::from_ref(&other)
+    ));
+    assert_eq!(rect, IRect::new(5, 0, 10, 5));
+}
@@ -0,0 +1,72 @@
+use Skia::region::{
+    skia_region_contains_point, skia_region_get_bounds, skia_region_get_rects,
+    skia_region_is_complex, skia_region_new, skia_region_op_rect, skia_region_set_path,
+    skia_region_set_rects,
+};
+use array_box::ArrayBox;
+use skia_safe::region::RegionOp;
+use skia_safe::{IRect, Path, Rect, Region};
+use value_box::BorrowedPtr;
+
+#[test]
+fn region_union_of_rects() {
+    let mut region = skia_region_new().with_value_ok(|region| region).unwrap();
+    let rects = ArrayBox::from_vector(vec![IRect::new(0, 0, 10, 10), IRect::new(20, 0, 30, 10)]);
+    assert!(skia_region_set_rects(
+        BorrowedPtr::from_mut(&mut region),
+        BorrowedPtr::from_ref(&rects)
+    ));
+
+    assert!(skia_region_is_complex(BorrowedPtr::from_ref(&region)));
+    assert!(skia_region_contains_point(
+        BorrowedPtr::from_ref(&region),
+        5,
+        5
+    ));
+    assert!(!skia_region_contains_point(
+        BorrowedPtr::from_ref(&region),
+        15,
+        5
+    ));
+    skia_region_get_bounds(BorrowedPtr::from_ref(&region))
+        .with_value_ok(|bounds| assert_eq!(bounds, IRect::new(0, 0, 30, 10)))
+        .unwrap();
+
+    let mut region_rects = ArrayBox::<IRect>::new();
+    skia_region_get_rects(
+        BorrowedPtr::from_ref(&region),
+        BorrowedPtr::from_mut(&mut region_rects),
+    );
+    assert_eq!(region_rects.to_slice(), rects.to_slice());
+}
+
+#[test]
+fn region_op_rect() {
+    let mut region = Region::from_rect(IRect::new(0, 0, 10, 10));
+    let rect = IRect::new(0, 0, 5, 10);
+    assert!(skia_region_op_rect(
+        BorrowedPtr::from_mut(&mut region),
+        BorrowedPtr::from_ref(&rect),
+        RegionOp::Difference
+    ));
+    assert_eq!(*region.bounds(), IRect::new(5, 0, 10, 10));
+
+    assert!(!skia_region_op_rect(
+        BorrowedPtr::from_mut(&mut region),
+        BorrowedPtr::from_ref(&rect),
+        RegionOp::Intersect
+    ));
+    assert!(region.is_empty());
+}
+
+#[test]
+fn region_set_path_without_clip() {
+    let mut region = Region::new();
+    let path = Path::rect(Rect::new(0.0, 0.0, 10.0, 10.0), None);
+    assert!(skia_region_set_path(
+        BorrowedPtr::from_mut(&mut region),
+        BorrowedPtr::from_ref(&path),
+        BorrowedPtr::null()
+    ));
+    assert_eq!(*region.bounds(), IRect::new(0, 0, 10, 10));
+}
@@ -1,8 +1,12 @@
 use Skia::rectangle::skia_rectangle_f32_set_ltrb;
 use Skia::rounded_rectangle::{
-    skia_rounded_rectangle_height, skia_rounded_rectangle_set_rect, skia_rounded_rectangle_width,
+    skia_rounded_rectangle_contains_point, skia_rounded_rectangle_get_radii,
+    skia_rounded_rectangle_height, skia_rounded_rectangle_is_valid,
+    skia_rounded_rectangle_set_radii, skia_rounded_rectangle_set_rect,
+    skia_rounded_rectangle_transform, skia_rounded_rectangle_width,
 };
-use skia_safe::{RRect, Rect};
+use skia_safe::rrect::Corner;
+use skia_safe::{Matrix, RRect, Rect, Vector};
 use value_box::BorrowedPtr;
 
 #[test]
@@ -36,3 +40,70 @@ fn set_rect() {
         50.0
     );
 }
+
+#[test]
+fn contains_point_respects_rounded_corners() {
+    let r_rect = RRect::new_rect_xy(Rect::new(0.0, 0.0, 100.0, 100.0), 20.0, 20.0);
+
+    assert!(skia_rounded_rectangle_contains_point(
+        BorrowedPtr::from_ref(&r_rect),
+        50.0,
+        50.0
+    ));
+    assert!(!skia_rounded_rectangle_contains_point(
+        BorrowedPtr::from_ref(&r_rect),
+        1.0,
+        1.0
+    ));
+    assert!(!skia_rounded_rectangle_contains_point(
+        BorrowedPtr::from_ref(&r_rect),
+        99.0,
+        99.0
+    ));
+    assert!(skia_rounded_rectangle_contains_point(
+        BorrowedPtr::from_ref(&r_rect),
+        10.0,
+        10.0
+    ));
+}
+
+#[test]
+fn set_radii_of_single_corner() {
+    let mut r_rect = RRect::new_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+    skia_rounded_rectangle_set_radii(
+        BorrowedPtr::from_mut(&mut r_rect),
+        Corner::LowerRight,
+        10.0,
+        5.0,
+    );
+
+    skia_rounded_rectangle_get_radii(BorrowedPtr::from_ref(&r_rect), Corner::LowerRight)
+        .with_value_ok(|radii| assert_eq!(radii, Vector::new(10.0, 5.0)))
+        .unwrap();
+    assert_eq!(r_rect.radii(Corner::UpperLeft), Vector::new(0.0, 0.0));
+    assert!(skia_rounded_rectangle_is_valid(BorrowedPtr::from_ref(
+        &r_rect
+    )));
+}
+
+#[test]
+fn transform_with_rotation_fails() {
+    let r_rect = RRect::new_rect_xy(Rect::new(0.0, 0.0, 100.0, 100.0), 20.0, 20.0);
+
+    let scale = Matrix::scale((2.0, 2.0));
+    skia_rounded_rectangle_transform(
+        BorrowedPtr::from_ref(&r_rect),
+        BorrowedPtr::from_ref(&scale),
+    )
+    .with_value_ok(|transformed| assert_eq!(transformed.width(), 200.0))
+    .unwrap();
+
+    let rotate = Matrix::rotate_deg(45.0);
+    assert!(
+        skia_rounded_rectangle_transform(
+            BorrowedPtr::from_ref(&r_rect),
+            BorrowedPtr::from_ref(&rotate),
+        )
+        .is_null()
+    );
+}
@@ -0,0 +1,44 @@
+use Skia::sampling_options::{
+    skia_sampling_options_drop, skia_sampling_options_is_cubic, skia_sampling_options_new,
+    skia_sampling_options_new_cubic_catmull_rom, skia_sampling_options_new_cubic_mitchell,
+};
+use skia_safe::{FilterMode, MipmapMode, SamplingOptions};
+use value_box::BorrowedPtr;
+
+#[test]
+fn sampling_options_cubic_presets() {
+    let mitchell = skia_sampling_options_new_cubic_mitchell();
+    mitchell
+        .with_value_ok(|mitchell| {
+            assert!(mitchell.use_cubic);
+            assert_eq!(mitchell.cubic.b, 1.0 / 3.0);
+            assert_eq!(mitchell.cubic.c, 1.0 / 3.0);
+        })
+        .unwrap();
+
+    let catmull_rom = skia_sampling_options_new_cubic_catmull_rom();
+    catmull_rom
+        .with_value_ok(|catmull_rom| {
+            assert!(catmull_rom.use_cubic);
+            assert_eq!(catmull_rom.cubic.b, 0.0);
+            assert_eq!(catmull_rom.cubic.c, 0.5);
+        })
+        .unwrap();
+}
+
+#[test]
+fn sampling_options_is_cubic() {
+    let linear = SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear);
+    assert!(!skia_sampling_options_is_cubic(BorrowedPtr::from_ref(
+        &linear
+    )));
+    assert!(!skia_sampling_options_is_cubic(BorrowedPtr::null()));
+}
+
+#[test]
+fn sampling_options_drop_accepts_owned_pointer() {
+    skia_sampling_options_drop(skia_sampling_options_new(
+        FilterMode::Nearest,
+        MipmapMode::None,
+    ));
+}
@@ -0,0 +1,179 @@
+use Skia::canvas::skia_canvas_read_pixels;
+use Skia::surface::{
+    skia_surface_get_surface_props, skia_surface_new_raster, skia_surface_read_pixels,
+    skia_surface_write_pixels,
+};
+use Skia::surface_props::{
+    skia_surface_props_get_flags, skia_surface_props_get_pixel_geometry, skia_surface_props_new,
+};
+use array_box::ArrayBox;
+use skia_safe::{
+    AlphaType, Color, ColorType, ImageInfo, PictureRecorder, PixelGeometry, Rect, Surface,
+    SurfaceProps, SurfacePropsFlags, surfaces,
+};
+use value_box::BorrowedPtr;
+
+fn rgba_info(width: i32, height: i32) -> ImageInfo {
+    ImageInfo::new(
+        (width, height),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        None,
+    )
+}
+
+/// An 8x8 white surface with red, green, blue and black pixels written at 3, 4
+fn surface_with_written_pixels() -> Surface {
+    let mut surface = surfaces::raster_n32_premul((8, 8)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+
+    let mut pixels = ArrayBox::from_vector(vec![
+        0xFF, 0x00, 0x00, 0xFF, 0x00, 0xFF, 0x00, 0xFF, // red, green
+        0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0xFF, // blue, black
+    ]);
+    assert!(skia_surface_write_pixels(
+        BorrowedPtr::from_mut(&mut surface),
+        3,
+        4,
+        BorrowedPtr::from_ref(&rgba_info(2, 2)),
+        BorrowedPtr::from_mut(&mut pixels),
+        8,
+    ));
+    surface
+}
+
+/// Returns the pixel at x, y of 4x4 RGBA pixels
+fn pixel_at(pixels: &ArrayBox<u8>, x: usize, y: usize) -> [u8; 4] {
+    let offset = (y * 4 + x) * 4;
+    pixels.to_slice()[offset..offset + 4].try_into().unwrap()
+}
+
+fn assert_written_pixels(pixels: &ArrayBox<u8>) {
+    assert_eq!(pixel_at(pixels, 0, 0), [0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(pixel_at(pixels, 1, 1), [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(pixel_at(pixels, 2, 1), [0x00, 0xFF, 0x00, 0xFF]);
+    assert_eq!(pixel_at(pixels, 1, 2), [0x00, 0x00, 0xFF, 0xFF]);
+    assert_eq!(pixel_at(pixels, 2, 2), [0x00, 0x00, 0x00, 0xFF]);
+    assert_eq!(pixel_at(pixels, 3, 3), [0xFF, 0xFF, 0xFF, 0xFF]);
+}
+
+#[test]
+fn surface_write_and_read_pixels() {
+    let mut surface = surface_with_written_pixels();
+
+    let mut pixels = ArrayBox::from_vector(vec![0u8; 4 * 4 * 4]);
+    let mut read_pixels = |x: i32, y: i32| {
+        skia_surface_read_pixels(
+            BorrowedPtr::from_mut(&mut surface),
+            x,
+            y,
+            BorrowedPtr::from_ref(&rgba_info(4, 4)),
+            BorrowedPtr::from_mut(&mut pixels),
+            16,
+        )
+    };
+    assert!(read_pixels(2, 3));
+    assert!(!read_pixels(8, 0));
+    assert!(!read_pixels(-4, -4));
+    assert_written_pixels(&pixels);
+}
+
+#[test]
+fn surface_write_pixels_outside_of_the_surface() {
+    let mut surface = surface_with_written_pixels();
+    let mut write_pixels = |x: i32, y: i32, pixels: Vec<u8>| {
+        skia_surface_write_pixels(
+            BorrowedPtr::from_mut(&mut surface),
+            x,
+            y,
+            BorrowedPtr::from_ref(&rgba_info(2, 2)),
+            BorrowedPtr::from_mut(&mut ArrayBox::from_vector(pixels)),
+            8,
+        )
+    };
+
+    assert!(!write_pixels(8, 0, vec![0; 16]));
+    assert!(!write_pixels(0, -2, vec![0; 16]));
+    // the pixels do not fit the image info
+    assert!(!write_pixels(0, 0, vec![0; 12]));
+}
+
+#[test]
+fn canvas_read_pixels() {
+    let mut surface = surface_with_written_pixels();
+
+    let mut pixels = ArrayBox::from_vector(vec![0u8; 4 * 4 * 4]);
+    let mut read_pixels = |x: i32, y: i32| {
+        skia_canvas_read_pixels(
+            BorrowedPtr::from_ref(surface.canvas()),
+            x,
+            y,
+            BorrowedPtr::from_ref(&rgba_info(4, 4)),
+            BorrowedPtr::from_mut(&mut pixels),
+            16,
+        )
+    };
+    assert!(read_pixels(2, 3));
+    assert!(!read_pixels(0, 8));
+    assert_written_pixels(&pixels);
+
+    // a recording canvas has no pixels to read
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 8.0, 8.0), false);
+    assert!(!skia_canvas_read_pixels(
+        BorrowedPtr::from_ref(canvas),
+        0,
+        0,
+        BorrowedPtr::from_ref(&rgba_info(4, 4)),
+        BorrowedPtr::from_mut(&mut pixels),
+        16,
+    ));
+}
+
+#[test]
+fn surface_new_raster_with_row_bytes_and_surface_props() {
+    let image_info = ImageInfo::new((8, 4), ColorType::RGBA8888, AlphaType::Premul, None);
+    let flags = SurfacePropsFlags::USE_DEVICE_INDEPENDENT_FONTS | SurfacePropsFlags::ALWAYS_DITHER;
+    let surface_props = skia_surface_props_new(flags.bits(), PixelGeometry::BGRV)
+        .with_value_ok(|surface_props| surface_props)
+        .unwrap();
+
+    let mut surface = skia_surface_new_raster(
+        BorrowedPtr::from_ref(&image_info),
+        48,
+        BorrowedPtr::from_ref(&surface_props),
+    )
+    .with_value_ok(|surface| surface)
+    .unwrap();
+    assert_eq!(surface.peek_pixels().unwrap().row_bytes(), 48);
+
+    let surface_props = skia_surface_get_surface_props(BorrowedPtr::from_ref(&surface))
+        .with_value_ok(|surface_props| surface_props)
+        .unwrap();
+    assert_eq!(
+        skia_surface_props_get_flags(BorrowedPtr::from_ref(&surface_props)),
+        flags.bits()
+    );
+    assert_eq!(
+        skia_surface_props_get_pixel_geometry(BorrowedPtr::from_ref(&surface_props)),
+        PixelGeometry::BGRV
+    );
+
+    // 0 row bytes are the minimal row bytes and default surface properties are used without props
+    skia_surface_new_raster(BorrowedPtr::from_ref(&image_info), 0, BorrowedPtr::null())
+        .with_value_ok(|mut surface| {
+            assert_eq!(surface.peek_pixels().unwrap().row_bytes(), 32);
+            assert_eq!(*surface.props(), SurfaceProps::default());
+        })
+        .unwrap();
+
+    // the rows must fit the width
+    assert!(
+        skia_surface_new_raster(
+            BorrowedPtr::from_ref(&image_info),
+            16,
+            BorrowedPtr::from_ref(&surface_props),
+        )
+        .is_null()
+    );
+}
@@ -0,0 +1,460 @@
+#![cfg(feature = "svg")]
+
+use Skia::svg::{
+    SvgDom, skia_canvas_render_svg_in_rect, skia_canvas_render_svg_node_in_rect,
+    skia_svg_canvas_end, skia_svg_canvas_end_to_bytes, skia_svg_canvas_end_to_file,
+    skia_svg_canvas_new_with_options, skia_svg_find_node_by_id, skia_svg_get_intrinsic_height,
+    skia_svg_get_intrinsic_width, skia_svg_get_view_box, skia_svg_node_get_fill,
+    skia_svg_node_get_visibility, skia_svg_node_set_fill_rgb, skia_svg_node_set_visibility,
+    skia_svg_parse_with_resources,
+};
+use Skia::svg_resources::{
+    SvgResourceProvider, skia_svg_resource_provider_add_resource, skia_svg_resource_provider_new,
+    skia_svg_resource_provider_set_allow_absolute_paths,
+    skia_svg_resource_provider_set_base_directory, skia_svg_resource_provider_set_callback,
+};
+use array_box::ArrayBox;
+use skia_safe::svg::{Canvas as SvgCanvas, Dom, NodeTag, Visibility};
+use skia_safe::{Color, EncodedImageFormat, FontMgr, Paint, Rect, Surface, surfaces};
+use string_box::StringBox;
+use value_box::{BorrowedPtr, OwnedPtr};
+
+const ICON: &str = r#"<?xml version="1.0"?>
+<!-- two squares -->
+<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 20 10">
+    <title id="title">Icon</title>
+    <rect id="left" x="0" y="0" width="10" height="10" fill="red"/>
+    <g id="group">
+        <rect id="right" x="10" y="0" width="10" height="10" fill="blue"/>
+    </g>
+</svg>"#;
+
+fn parse(svg: &str) -> SvgDom {
+    SvgDom::new(svg, Dom::from_str(svg, FontMgr::default()).unwrap())
+}
+
+fn render_in_rect(dom: &mut SvgDom, rect: Rect) -> Surface {
+    let mut surface = surfaces::raster_n32_premul((40, 40)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+    skia_canvas_render_svg_in_rect(
+        BorrowedPtr::from_ref(surface.canvas()),
+        BorrowedPtr::from_mut(dom),
+        BorrowedPtr::from_ref(&rect),
+    );
+    surface
+}
+
+fn color_at(surface: &mut Surface, x: i32, y: i32) -> Color {
+    surface.peek_pixels().unwrap().get_color((x, y))
+}
+
+#[test]
+fn svg_intrinsic_size_and_view_box() {
+    let dom = parse(ICON);
+    assert_eq!(
+        skia_svg_get_intrinsic_width(BorrowedPtr::from_ref(&dom)),
+        20.0
+    );
+    assert_eq!(
+        skia_svg_get_intrinsic_height(BorrowedPtr::from_ref(&dom)),
+        10.0
+    );
+
+    skia_svg_get_view_box(BorrowedPtr::from_ref(&dom))
+        .with_value_ok(|view_box| assert_eq!(view_box, Rect::new(0.0, 0.0, 20.0, 10.0)))
+        .unwrap();
+
+    let dom = parse(r#"<svg xmlns="http://www.w3.org/2000/svg" width="5" height="5"/>"#);
+    assert!(skia_svg_get_view_box(BorrowedPtr::from_ref(&dom)).is_null());
+}
+
+#[test]
+fn svg_render_in_rect_preserves_aspect_ratio() {
+    let mut dom = parse(ICON);
+
+    // the 2:1 icon is centered vertically in the square
+    let mut surface = render_in_rect(&mut dom, Rect::new(0.0, 0.0, 40.0, 40.0));
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+    assert_eq!(color_at(&mut surface, 5, 15), Color::RED);
+    assert_eq!(color_at(&mut surface, 35, 25), Color::BLUE);
+    assert_eq!(color_at(&mut surface, 35, 35), Color::WHITE);
+
+    // the size of the document is restored after rendering
+    assert_eq!(
+        skia_svg_get_intrinsic_width(BorrowedPtr::from_ref(&dom)),
+        20.0
+    );
+    assert_eq!(
+        skia_svg_get_intrinsic_height(BorrowedPtr::from_ref(&dom)),
+        10.0
+    );
+
+    let mut dom = parse(&ICON.replace("viewBox", "preserveAspectRatio=\"none\" viewBox"));
+    let mut surface = render_in_rect(&mut dom, Rect::new(0.0, 0.0, 40.0, 40.0));
+    assert_eq!(color_at(&mut surface, 5, 5), Color::RED);
+    assert_eq!(color_at(&mut surface, 35, 35), Color::BLUE);
+}
+
+#[test]
+fn svg_find_node_by_id_and_set_attributes() {
+    let mut dom = parse(ICON);
+    let find = |dom: &SvgDom, id: &str| {
+        skia_svg_find_node_by_id(
+            BorrowedPtr::from_ref(dom),
+            BorrowedPtr::from_ref(&StringBox::from_string(id.to_string())),
+        )
+    };
+
+    assert!(find(&dom, "title").is_null());
+    assert!(find(&dom, "missing").is_null());
+
+    let mut right = find(&dom, "right").with_value_ok(|node| node).unwrap();
+    skia_svg_node_get_fill(BorrowedPtr::from_ref(&right))
+        .with_value_ok(|fill| assert_eq!(fill, Color::BLUE))
+        .unwrap();
+    skia_svg_node_set_fill_rgb(BorrowedPtr::from_mut(&mut right), 0, 255, 0);
+
+    let mut left = find(&dom, "left").with_value_ok(|node| node).unwrap();
+    assert_eq!(
+        skia_svg_node_get_visibility(BorrowedPtr::from_ref(&left)),
+        Visibility::Inherit
+    );
+    skia_svg_node_set_visibility(BorrowedPtr::from_mut(&mut left), Visibility::Hidden);
+    assert_eq!(
+        skia_svg_node_get_visibility(BorrowedPtr::from_ref(&left)),
+        Visibility::Hidden
+    );
+
+    let mut surface = render_in_rect(&mut dom, Rect::new(0.0, 0.0, 40.0, 20.0));
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+    assert_eq!(color_at(&mut surface, 35, 5), Color::GREEN);
+}
+
+#[test]
+fn svg_render_node_in_rect() {
+    let mut dom = parse(ICON);
+    let id = StringBox::from_string("group".to_string());
+    let rect = Rect::new(0.0, 0.0, 40.0, 20.0);
+
+    let mut surface = surfaces::raster_n32_premul((40, 40)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+    skia_canvas_render_svg_node_in_rect(
+        BorrowedPtr::from_ref(surface.canvas()),
+        BorrowedPtr::from_mut(&mut dom),
+        BorrowedPtr::from_ref(&id),
+        BorrowedPtr::from_ref(&rect),
+    );
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+    assert_eq!(color_at(&mut surface, 35, 5), Color::BLUE);
+
+    // the other nodes are shown again
+    let mut surface = render_in_rect(&mut dom, rect);
+    assert_eq!(color_at(&mut surface, 5, 5), Color::RED);
+}
+
+/// The tag of the node found for the id
+fn find_tag(dom: &SvgDom, id: &str) -> Option<NodeTag> {
+    skia_svg_find_node_by_id(
+        BorrowedPtr::from_ref(dom),
+        BorrowedPtr::from_ref(&StringBox::from_string(id.to_string())),
+    )
+    .with_value_ok(|node| node.tag())
+    .ok()
+}
+
+#[test]
+fn svg_find_node_by_id_with_references_and_character_data() {
+    let dom = parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+    <style><![CDATA[ rect { fill: red } <rect id="in-data"/> ]]></style>
+    <rect id="a&amp;b" width="10" height="10"/>
+    <circle id="&#99;ircle&#x21;" r="5"/>
+</svg>"#,
+    );
+    assert_eq!(find_tag(&dom, "a&b"), Some(NodeTag::Rect));
+    assert_eq!(find_tag(&dom, "circle!"), Some(NodeTag::Circle));
+    assert_eq!(find_tag(&dom, "in-data"), None);
+}
+
+#[test]
+fn svg_find_node_by_id_skips_comments_and_processing_instructions() {
+    let dom = parse(
+        r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+    <!-- <rect id="commented"/> -->
+    <?note <circle id="instruction"?>
+    <rect id="rect" width="10" height="10"/>
+</svg>"#,
+    );
+    assert_eq!(find_tag(&dom, "commented"), None);
+    assert_eq!(find_tag(&dom, "instruction"), None);
+    assert_eq!(find_tag(&dom, "rect"), Some(NodeTag::Rect));
+}
+
+#[test]
+fn svg_find_node_by_id_with_prefixed_tags() {
+    let dom = parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:svg="http://www.w3.org/2000/svg" width="20" height="10">
+    <svg:circle id="prefixed" r="5"/>
+    <rect id="rect" width="10" height="10"/>
+</svg>"#,
+    );
+    assert_eq!(find_tag(&dom, "prefixed"), None);
+    // whether the DOM has a node for the prefixed element or not, the id never leads to it
+    assert!(matches!(find_tag(&dom, "rect"), None | Some(NodeTag::Rect)));
+}
+
+#[test]
+fn svg_find_node_by_id_with_entity_declarations() {
+    // the entities may expand to elements, so the ids are not looked up
+    let dom = parse(
+        r#"<!DOCTYPE svg [ <!ENTITY square "<rect width='5' height='5'/>"> ]>
+<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+    &square;
+    <circle id="circle" r="5"/>
+</svg>"#,
+    );
+    assert_eq!(find_tag(&dom, "circle"), None);
+}
+
+#[test]
+fn svg_find_node_by_id_when_the_dom_supports_other_elements() {
+    let dom_of = |svg: &str| Dom::from_str(svg, FontMgr::default()).unwrap();
+
+    // the linked Skia does not create a node for an element known to have one
+    let dom = SvgDom::new(
+        r#"<svg><circle r="5"/><rect id="rect" width="10" height="10"/></svg>"#,
+        dom_of(r#"<svg><rect width="10" height="10"/></svg>"#),
+    );
+    assert_eq!(find_tag(&dom, "rect"), None);
+
+    // the linked Skia creates a node for an element not known to have one
+    let mut dom = SvgDom::new(
+        r#"<svg><unknown/><rect id="rect" width="10" height="10" fill="red"/></svg>"#,
+        dom_of(r#"<svg><circle r="5"/><rect width="10" height="10" fill="red"/></svg>"#),
+    );
+    assert_eq!(find_tag(&dom, "rect"), None);
+
+    // nothing is rendered for an id that does not lead to its element
+    let mut surface = surfaces::raster_n32_premul((40, 40)).unwrap();
+    surface.canvas().clear(Color::WHITE);
+    skia_canvas_render_svg_node_in_rect(
+        BorrowedPtr::from_ref(surface.canvas()),
+        BorrowedPtr::from_mut(&mut dom),
+        BorrowedPtr::from_ref(&StringBox::from_string("rect".to_string())),
+        BorrowedPtr::from_ref(&Rect::new(0.0, 0.0, 10.0, 10.0)),
+    );
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+}
+
+fn image(href: &str) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="10" height="10">
+    <image xlink:href="{href}" width="10" height="10"/>
+</svg>"#
+    )
+}
+
+fn red_png() -> Vec<u8> {
+    let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+    surface.canvas().clear(Color::RED);
+    let data = surface
+        .image_snapshot()
+        .encode(None, EncodedImageFormat::PNG, None)
+        .unwrap();
+    data.as_bytes().to_vec()
+}
+
+fn render_with_resources(configure: impl FnOnce(&mut SvgResourceProvider)) -> Surface {
+    render_image_with_resources("images/red.png", configure)
+}
+
+fn render_image_with_resources(
+    href: &str,
+    configure: impl FnOnce(&mut SvgResourceProvider),
+) -> Surface {
+    let font_mgr = FontMgr::default();
+    let mut resource_provider = skia_svg_resource_provider_new(BorrowedPtr::from_ref(&font_mgr))
+        .with_value_ok(|resource_provider| resource_provider)
+        .unwrap();
+    configure(&mut resource_provider);
+
+    let svg = StringBox::from_string(image(href));
+    let mut dom = skia_svg_parse_with_resources(
+        BorrowedPtr::from_ref(&svg),
+        BorrowedPtr::from_ref(&resource_provider),
+    )
+    .with_value_ok(|dom| dom)
+    .unwrap();
+    render_in_rect(&mut dom, Rect::new(0.0, 0.0, 10.0, 10.0))
+}
+
+#[test]
+fn svg_parse_with_in_memory_resources() {
+    let mut surface = render_with_resources(|resource_provider| {
+        let name = StringBox::from_string("images/red.png".to_string());
+        let bytes = ArrayBox::from_vector(red_png());
+        skia_svg_resource_provider_add_resource(
+            BorrowedPtr::from_mut(resource_provider),
+            BorrowedPtr::from_ref(&name),
+            BorrowedPtr::from_ref(&bytes),
+        );
+    });
+    assert_eq!(color_at(&mut surface, 5, 5), Color::RED);
+}
+
+#[test]
+fn svg_parse_with_base_directory() {
+    let directory = std::env::temp_dir().join(format!("svg_resources_{}", std::process::id()));
+    std::fs::create_dir_all(directory.join("images")).unwrap();
+    std::fs::write(directory.join("images").join("red.png"), red_png()).unwrap();
+
+    let mut surface = render_with_resources(|resource_provider| {
+        let base_directory = StringBox::from_string(directory.to_string_lossy().to_string());
+        skia_svg_resource_provider_set_base_directory(
+            BorrowedPtr::from_mut(resource_provider),
+            BorrowedPtr::from_ref(&base_directory),
+        );
+    });
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(color_at(&mut surface, 5, 5), Color::RED);
+
+    let mut surface = render_with_resources(|_| {});
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+}
+
+#[test]
+fn svg_resources_are_not_read_outside_of_the_base_directory() {
+    let directory =
+        std::env::temp_dir().join(format!("svg_resources_outside_{}", std::process::id()));
+    let base_directory = directory.join("base");
+    std::fs::create_dir_all(&base_directory).unwrap();
+    std::fs::write(directory.join("red.png"), red_png()).unwrap();
+    let outside = directory.join("red.png").to_string_lossy().to_string();
+
+    let set_base_directory = |resource_provider: &mut SvgResourceProvider| {
+        let base_directory = StringBox::from_string(base_directory.to_string_lossy().to_string());
+        skia_svg_resource_provider_set_base_directory(
+            BorrowedPtr::from_mut(resource_provider),
+            BorrowedPtr::from_ref(&base_directory),
+        );
+    };
+
+    let mut surface = render_image_with_resources("../red.png", set_base_directory);
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+    let mut surface = render_image_with_resources(&outside, set_base_directory);
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+    let mut surface = render_image_with_resources(&format!("file://{outside}"), set_base_directory);
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+
+    // unless absolute paths are allowed
+    let mut surface = render_image_with_resources(&outside, |resource_provider| {
+        set_base_directory(resource_provider);
+        skia_svg_resource_provider_set_allow_absolute_paths(
+            BorrowedPtr::from_mut(resource_provider),
+            true,
+        );
+    });
+    assert_eq!(color_at(&mut surface, 5, 5), Color::RED);
+
+    // no file is read without a base directory, even if absolute paths are allowed
+    let mut surface = render_image_with_resources(&outside, |_| {});
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+    let mut surface = render_image_with_resources(&outside, |resource_provider| {
+        skia_svg_resource_provider_set_allow_absolute_paths(
+            BorrowedPtr::from_mut(resource_provider),
+            true,
+        );
+    });
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+}
+
+extern "C" fn provide_red_png(
+    name: BorrowedPtr<StringBox>,
+    mut bytes: BorrowedPtr<ArrayBox<u8>>,
+) -> bool {
+    let is_red_png = name
+        .with_ref_ok(|name| name.as_str() == "images/red.png")
+        .unwrap_or(false);
+    if is_red_png {
+        bytes
+            .with_mut_ok(|bytes| bytes.set_vector(red_png()))
+            .unwrap();
+    }
+    is_red_png
+}
+
+#[test]
+fn svg_parse_with_resource_callback() {
+    let mut surface = render_with_resources(|resource_provider| {
+        skia_svg_resource_provider_set_callback(
+            BorrowedPtr::from_mut(resource_provider),
+            Some(provide_red_png),
+        );
+    });
+    assert_eq!(color_at(&mut surface, 5, 5), Color::RED);
+
+    // a null callback removes the previous one
+    let mut surface = render_with_resources(|resource_provider| {
+        skia_svg_resource_provider_set_callback(
+            BorrowedPtr::from_mut(resource_provider),
+            Some(provide_red_png),
+        );
+        skia_svg_resource_provider_set_callback(BorrowedPtr::from_mut(resource_provider), None);
+    });
+    assert_eq!(color_at(&mut surface, 5, 5), Color::WHITE);
+}
+
+fn new_svg_canvas_with_circle(no_pretty_xml: bool) -> OwnedPtr<SvgCanvas> {
+    let svg_canvas =
+        skia_svg_canvas_new_with_options(0.0, 0.0, 20.0, 20.0, true, no_pretty_xml, false)
+            .with_value_ok(|svg_canvas| svg_canvas)
+            .unwrap();
+    svg_canvas.draw_circle((10, 10), 10.0, &Paint::default());
+    OwnedPtr::new(svg_canvas)
+}
+
+#[test]
+fn svg_canvas_end_with_options() {
+    let mut svg = StringBox::new();
+    assert!(skia_svg_canvas_end(
+        new_svg_canvas_with_circle(false),
+        BorrowedPtr::from_mut(&mut svg)
+    ));
+    let pretty = svg.to_string();
+    assert!(pretty.contains(r#"<ellipse cx="10" cy="10" rx="10" ry="10"/>"#));
+    assert!(pretty.contains("\n"));
+
+    let mut bytes = ArrayBox::<u8>::new();
+    skia_svg_canvas_end_to_bytes(
+        new_svg_canvas_with_circle(true),
+        BorrowedPtr::from_mut(&mut bytes),
+    );
+    let compact = String::from_utf8(bytes.to_slice().to_vec()).unwrap();
+    assert!(compact.contains(r#"<ellipse cx="10" cy="10" rx="10" ry="10"/>"#));
+    assert!(compact.len() < pretty.len());
+}
+
+#[test]
+fn svg_canvas_end_to_file() {
+    let path = std::env::temp_dir().join(format!("svg_canvas_{}.svg", std::process::id()));
+    assert!(skia_svg_canvas_end_to_file(
+        new_svg_canvas_with_circle(false),
+        BorrowedPtr::from_ref(&StringBox::from_string(path.to_string_lossy().to_string())),
+    ));
+    let written = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(written.contains("<svg"));
+    assert!(written.contains(r#"<ellipse cx="10" cy="10" rx="10" ry="10"/>"#));
+
+    // the directory of the file does not exist
+    let path = std::env::temp_dir()
+        .join(format!("svg_canvas_missing_{}", std::process::id()))
+        .join("circle.svg");
+    assert!(!skia_svg_canvas_end_to_file(
+        new_svg_canvas_with_circle(false),
+        BorrowedPtr::from_ref(&StringBox::from_string(path.to_string_lossy().to_string())),
+    ));
+    assert!(!path.exists());
+}
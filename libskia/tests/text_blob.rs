@@ -0,0 +1,207 @@
+use Skia::text::text_blob::{
+    skia_text_blob_builder_alloc_run_pos, skia_text_blob_builder_alloc_run_pos_h,
+    skia_text_blob_builder_alloc_run_rsxform, skia_text_blob_builder_alloc_run_text_pos,
+    skia_text_blob_builder_make, skia_text_blob_from_text_on_path, skia_text_blob_get_bounds,
+    skia_text_blob_get_intercepts,
+};
+use array_box::ArrayBox;
+use skia_safe::utils::text_utils::Align;
+use skia_safe::{Font, FontMgr, FontStyle, Paint, Path, Point, Rect, TextBlob, TextBlobBuilder};
+use string_box::StringBox;
+use value_box::BorrowedPtr;
+
+fn font() -> Font {
+    let typeface = FontMgr::default()
+        .legacy_make_typeface(None, FontStyle::default())
+        .unwrap();
+    Font::from_typeface(typeface, 20.0)
+}
+
+fn make(builder: &mut TextBlobBuilder) -> Option<TextBlob> {
+    let text_blob = skia_text_blob_builder_make(BorrowedPtr::from_mut(builder));
+    (!text_blob.is_null()).then(|| text_blob.with_value_ok(|text_blob| text_blob).unwrap())
+}
+
+fn bounds(text_blob: &TextBlob) -> Rect {
+    let mut bounds = Rect::default();
+    skia_text_blob_get_bounds(
+        BorrowedPtr::from_ref(text_blob),
+        BorrowedPtr::from_mut(&mut bounds),
+    );
+    bounds
+}
+
+#[test]
+fn text_blob_builder_positioned_runs() {
+    let font = font();
+    let glyphs = ArrayBox::from_vector(font.str_to_glyphs_vec("ab"));
+    let mut builder = TextBlobBuilder::new();
+
+    let xs = ArrayBox::from_vector(vec![0.0, 200.0]);
+    assert!(skia_text_blob_builder_alloc_run_pos_h(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&xs),
+        20.0,
+    ));
+    let positions = ArrayBox::from_vector(vec![Point::new(0.0, 100.0), Point::new(10.0, 100.0)]);
+    assert!(skia_text_blob_builder_alloc_run_pos(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&positions),
+    ));
+
+    let text_blob = make(&mut builder).unwrap();
+    let bounds = bounds(&text_blob);
+    assert!(bounds.right > 200.0);
+    assert!(bounds.bottom > 100.0);
+
+    // the builder is reset after making a blob
+    assert!(make(&mut builder).is_none());
+}
+
+#[test]
+fn text_blob_builder_rejects_mismatched_runs() {
+    let font = font();
+    let glyphs = ArrayBox::from_vector(font.str_to_glyphs_vec("ab"));
+    let mut builder = TextBlobBuilder::new();
+
+    let xs = ArrayBox::from_vector(vec![0.0]);
+    assert!(!skia_text_blob_builder_alloc_run_pos_h(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&xs),
+        20.0,
+    ));
+    let xforms = ArrayBox::from_vector(vec![1.0, 0.0, 0.0, 20.0, 1.0, 0.0]);
+    assert!(!skia_text_blob_builder_alloc_run_rsxform(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&xforms),
+    ));
+
+    let positions = ArrayBox::from_vector(vec![Point::new(0.0, 20.0), Point::new(10.0, 20.0)]);
+    let text = StringBox::from_string("ab".to_string());
+    let clusters = ArrayBox::from_vector(vec![0u32, 2]);
+    assert!(!skia_text_blob_builder_alloc_run_text_pos(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&positions),
+        BorrowedPtr::from_ref(&text),
+        BorrowedPtr::from_ref(&clusters),
+    ));
+
+    assert!(make(&mut builder).is_none());
+}
+
+#[test]
+fn text_blob_builder_rsxform_and_text_runs() {
+    let font = font();
+    let glyphs = ArrayBox::from_vector(font.str_to_glyphs_vec("ab"));
+    let mut builder = TextBlobBuilder::new();
+
+    // the second glyph is rotated by 90 degrees
+    let xforms = ArrayBox::from_vector(vec![1.0, 0.0, 0.0, 20.0, 0.0, 1.0, 100.0, 20.0]);
+    assert!(skia_text_blob_builder_alloc_run_rsxform(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&xforms),
+    ));
+
+    let positions = ArrayBox::from_vector(vec![Point::new(0.0, 50.0), Point::new(10.0, 50.0)]);
+    let text = StringBox::from_string("ab".to_string());
+    let clusters = ArrayBox::from_vector(vec![0u32, 1]);
+    assert!(skia_text_blob_builder_alloc_run_text_pos(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&positions),
+        BorrowedPtr::from_ref(&text),
+        BorrowedPtr::from_ref(&clusters),
+    ));
+
+    let text_blob = make(&mut builder).unwrap();
+    assert!(bounds(&text_blob).right > 100.0);
+}
+
+#[test]
+fn text_blob_intercepts() {
+    let font = font();
+    let glyphs = font.str_to_glyphs_vec("oo");
+    let glyph_count = glyphs.len();
+    let glyphs = ArrayBox::from_vector(glyphs);
+    let xs = ArrayBox::from_vector(vec![0.0, 50.0]);
+    let mut builder = TextBlobBuilder::new();
+    assert!(skia_text_blob_builder_alloc_run_pos_h(
+        BorrowedPtr::from_mut(&mut builder),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(&glyphs),
+        BorrowedPtr::from_ref(&xs),
+        20.0,
+    ));
+    let text_blob = make(&mut builder).unwrap();
+
+    // a band through the middle of the glyphs above the baseline
+    let mut intercepts = ArrayBox::<f32>::new();
+    skia_text_blob_get_intercepts(
+        BorrowedPtr::from_ref(&text_blob),
+        14.0,
+        16.0,
+        BorrowedPtr::<Paint>::null(),
+        BorrowedPtr::from_mut(&mut intercepts),
+    );
+    assert_eq!(intercepts.length % 2, 0);
+    assert!(intercepts.length >= glyph_count * 2);
+    assert!(intercepts.to_slice().iter().any(|x| *x > 50.0));
+
+    // nothing below the baseline
+    skia_text_blob_get_intercepts(
+        BorrowedPtr::from_ref(&text_blob),
+        30.0,
+        32.0,
+        BorrowedPtr::<Paint>::null(),
+        BorrowedPtr::from_mut(&mut intercepts),
+    );
+    assert_eq!(intercepts.length, 0);
+}
+
+fn text_on_path(text: &str, path: &Path, offset: f32, align: Align) -> Option<TextBlob> {
+    let font = font();
+    let text = StringBox::from_string(text.to_string());
+    let text_blob = skia_text_blob_from_text_on_path(
+        BorrowedPtr::from_ref(&text),
+        BorrowedPtr::from_ref(&font),
+        BorrowedPtr::from_ref(path),
+        offset,
+        align,
+    );
+    (!text_blob.is_null()).then(|| text_blob.with_value_ok(|text_blob| text_blob).unwrap())
+}
+
+#[test]
+fn text_blob_from_text_on_path() {
+    let mut line = Path::new();
+    line.move_to((0.0, 50.0)).line_to((400.0, 50.0));
+
+    let left = bounds(&text_on_path("label", &line, 100.0, Align::Left).unwrap());
+    let center = bounds(&text_on_path("label", &line, 100.0, Align::Center).unwrap());
+    let right = bounds(&text_on_path("label", &line, 100.0, Align::Right).unwrap());
+    assert!(left.left >= 99.0 && left.bottom > 50.0 - 1.0 && left.top < 50.0);
+    assert!(center.left < 100.0 && center.right > 100.0);
+    assert!(right.right <= 101.0);
+
+    // the glyphs past the end of the path are left out
+    assert!(text_on_path("label", &line, 500.0, Align::Left).is_none());
+
+    // a vertical path rotates the glyphs
+    let mut vertical = Path::new();
+    vertical.move_to((50.0, 0.0)).line_to((50.0, 400.0));
+    let rotated = bounds(&text_on_path("label", &vertical, 0.0, Align::Left).unwrap());
+    assert!(rotated.height() > rotated.width());
+}
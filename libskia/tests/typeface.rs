@@ -0,0 +1,158 @@
+use Skia::text::typeface::{
+    skia_typeface_clone_with_variations, skia_typeface_copy_table_data, skia_typeface_count_glyphs,
+    skia_typeface_count_tables, skia_typeface_default, skia_typeface_deserialize,
+    skia_typeface_get_localized_family_name, skia_typeface_get_localized_family_name_count,
+    skia_typeface_get_named_instance_count, skia_typeface_get_post_script_name,
+    skia_typeface_get_table_tags, skia_typeface_get_units_per_em,
+    skia_typeface_get_variation_axis_count, skia_typeface_get_variation_axis_tag,
+    skia_typeface_get_variation_design_position, skia_typeface_is_a_color_font,
+    skia_typeface_serialize,
+};
+use array_box::ArrayBox;
+use skia_safe::typeface::SerializeBehavior;
+use skia_safe::{FontMgr, Typeface};
+use string_box::StringBox;
+use value_box::BorrowedPtr;
+
+fn default_typeface() -> Typeface {
+    skia_typeface_default()
+        .with_value_ok(|typeface| typeface)
+        .unwrap()
+}
+
+#[test]
+fn typeface_without_variations() {
+    let typeface = default_typeface();
+    assert_eq!(
+        skia_typeface_get_variation_axis_count(BorrowedPtr::from_ref(&typeface)),
+        0
+    );
+    assert_eq!(
+        skia_typeface_get_variation_axis_tag(BorrowedPtr::from_ref(&typeface), 0),
+        0
+    );
+    assert_eq!(
+        skia_typeface_get_named_instance_count(BorrowedPtr::from_ref(&typeface)),
+        0
+    );
+
+    let mut axis_tags = ArrayBox::<u32>::new();
+    let mut values = ArrayBox::<f32>::new();
+    skia_typeface_get_variation_design_position(
+        BorrowedPtr::from_ref(&typeface),
+        BorrowedPtr::from_mut(&mut axis_tags),
+        BorrowedPtr::from_mut(&mut values),
+    );
+    assert_eq!(axis_tags.length, 0);
+    assert_eq!(values.length, 0);
+}
+
+#[test]
+fn typeface_clone_with_variations_needs_a_value_per_tag() {
+    let typeface = default_typeface();
+    let axis_tags = ArrayBox::from_vector(vec![u32::from_be_bytes(*b"wght")]);
+    let values = ArrayBox::<f32>::new();
+    assert!(
+        skia_typeface_clone_with_variations(
+            BorrowedPtr::from_ref(&typeface),
+            BorrowedPtr::from_ref(&axis_tags),
+            BorrowedPtr::from_ref(&values),
+        )
+        .is_null()
+    );
+}
+
+#[test]
+fn typeface_tables() {
+    let typeface = default_typeface();
+    assert!(skia_typeface_count_glyphs(BorrowedPtr::from_ref(&typeface)) > 0);
+    assert!(skia_typeface_get_units_per_em(BorrowedPtr::from_ref(&typeface)) > 0);
+    assert!(!skia_typeface_is_a_color_font(BorrowedPtr::from_ref(
+        &typeface
+    )));
+
+    let mut tags = ArrayBox::<u32>::new();
+    skia_typeface_get_table_tags(
+        BorrowedPtr::from_ref(&typeface),
+        BorrowedPtr::from_mut(&mut tags),
+    );
+    assert_eq!(
+        tags.length,
+        skia_typeface_count_tables(BorrowedPtr::from_ref(&typeface))
+    );
+    let head = u32::from_be_bytes(*b"head");
+    assert!(tags.to_slice().contains(&head));
+
+    let mut data = ArrayBox::<u8>::new();
+    assert!(skia_typeface_copy_table_data(
+        BorrowedPtr::from_ref(&typeface),
+        head,
+        BorrowedPtr::from_mut(&mut data),
+    ));
+    // the magic number of the font header
+    assert_eq!(data.to_slice()[12..16], [0x5F, 0x0F, 0x3C, 0xF5]);
+
+    assert!(!skia_typeface_copy_table_data(
+        BorrowedPtr::from_ref(&typeface),
+        u32::from_be_bytes(*b"none"),
+        BorrowedPtr::from_mut(&mut data),
+    ));
+}
+
+#[test]
+fn typeface_names() {
+    let typeface = default_typeface();
+    let count = skia_typeface_get_localized_family_name_count(BorrowedPtr::from_ref(&typeface));
+    assert!(count > 0);
+
+    let mut name = StringBox::new();
+    let mut language = StringBox::new();
+    assert!(skia_typeface_get_localized_family_name(
+        BorrowedPtr::from_ref(&typeface),
+        0,
+        BorrowedPtr::from_mut(&mut name),
+        BorrowedPtr::from_mut(&mut language),
+    ));
+    assert!(!name.to_string().is_empty());
+    assert!(!skia_typeface_get_localized_family_name(
+        BorrowedPtr::from_ref(&typeface),
+        count,
+        BorrowedPtr::from_mut(&mut name),
+        BorrowedPtr::from_mut(&mut language),
+    ));
+
+    let mut post_script_name = StringBox::new();
+    assert!(skia_typeface_get_post_script_name(
+        BorrowedPtr::from_ref(&typeface),
+        BorrowedPtr::from_mut(&mut post_script_name),
+    ));
+    assert!(!post_script_name.to_string().contains(' '));
+}
+
+#[test]
+fn typeface_serialize_and_deserialize() {
+    let typeface = default_typeface();
+    let mut data = ArrayBox::<u8>::new();
+    skia_typeface_serialize(
+        BorrowedPtr::from_ref(&typeface),
+        SerializeBehavior::DoIncludeData,
+        BorrowedPtr::from_mut(&mut data),
+    );
+    assert!(data.length > 0);
+
+    let deserialized =
+        skia_typeface_deserialize(BorrowedPtr::from_ref(&data), BorrowedPtr::<FontMgr>::null())
+            .with_value_ok(|typeface| typeface)
+            .unwrap();
+    assert_eq!(deserialized.family_name(), typeface.family_name());
+    assert_eq!(deserialized.count_glyphs(), typeface.count_glyphs());
+
+    let garbage = ArrayBox::from_vector(vec![1u8, 2, 3]);
+    assert!(
+        skia_typeface_deserialize(
+            BorrowedPtr::from_ref(&garbage),
+            BorrowedPtr::<FontMgr>::null(),
+        )
+        .is_null()
+    );
+}